@@ -106,17 +106,38 @@ impl Component for App {
                 "id": "Task 9",
                 "progress": 0,
                 "isDisabled": true,
-                "type": "task"
+                "type": "task",
+                "styles": {
+                    "backgroundColor": "#c9e4de",
+                    "progressColor": "#5fa58f"
+                }
             }
         ]"###;
 
         let tasks_: Vec<Task> = serde_json::from_str(tasks_js).unwrap();
+        let markers_js = r###"[
+            {
+                "date": "2023-04-20 00:00:00",
+                "label": "Code freeze",
+                "dash": "dashed"
+            },
+            {
+                "date": "2023-05-01 00:00:00",
+                "end": "2023-05-14 00:00:00",
+                "label": "Sprint 14",
+                "color": "#3498db"
+            }
+        ]"###;
+        let display_option = rgantt::schemas::DisplayOption {
+            markers: serde_json::from_str(markers_js).ok(),
+            ..rgantt::schemas::DisplayOption::default()
+        };
 
         html! {
             <>
                 <rgantt::Gantt
                         event_option = {rgantt::schemas::EventOption::default()}
-                        display_option = {rgantt::schemas::DisplayOption::default()}
+                        display_option = {display_option}
                         style_option = {rgantt::schemas::StylingOption::default()}
                         tasks = {tasks_}
                 />
@@ -1,17 +1,36 @@
+pub mod locale;
+pub mod mspdi;
 pub mod schemas;
 mod svg_view;
 mod table;
+pub mod theme;
+pub mod time_zone;
+pub mod view_mode;
 
 #[macro_use]
 mod macros;
 
-use chrono::{NaiveDateTime, Utc};
-use yew::{html, Component, Context, Html};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ops::Range;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-use self::schemas::{BarTask, DateSetup, ViewMode};
+use chrono::NaiveDateTime;
+use wasm_bindgen::JsCast;
+use yew::{html, Component, Context, Html, TargetCast};
+
+use self::locale::Locale;
+use self::schemas::{BarEvent, BarSegment, BarTask, DateSetup, Row, TaskType, ViewMode};
+use self::theme::{var, Theme};
+use self::time_zone::DisplayTimeZone;
+use self::view_mode::{Scale, ScaleOptions, TimeScale, ZOOM_LEVELS};
+
+/// Numbers the charts on a page, each one scopes its theme variables by its own class.
+static INSTANCES: AtomicUsize = AtomicUsize::new(0);
 
 #[derive(Default)]
 pub struct Gantt {
+    // tasks as edited on the chart, replaced by new `tasks` props
+    tasks: Vec<schemas::Task>,
     grid_props: schemas::GridProps,
     calendar_props: schemas::CalendarProps,
     bar_props: schemas::TaskGanttContentProps,
@@ -19,87 +38,555 @@ pub struct Gantt {
     gantt_height: f64,
     scroll_y: f64,
     scroll_x: f64,
+    rtl: bool,
+    theme: Theme,
+    theme_class: String,
+    // zoom state, starts at the view mode and column width of the props
+    view_mode: ViewMode,
+    column_width: f64,
+    zoom_range: Option<(NaiveDateTime, NaiveDateTime)>,
+    // rows and columns in the DOM, only the visible ones and an overscan margin
+    viewport: schemas::Viewport,
+    visible_rows: Range<usize>,
+    visible_columns: Range<usize>,
+    // task, segment and layout of the segment being dragged
+    drag: Option<(usize, usize, BarSegment)>,
+    collapsed_groups: HashSet<String>,
+    // widths set by resizing the table, in px
+    column_widths: BTreeMap<String, f64>,
+    table_width: Option<f64>,
+    // column and direction of the sort, descending for `true`
+    sort: Option<(String, bool)>,
+    // filter set through the handle, replaces the `filter` display option
+    filter: Option<schemas::TaskFilter>,
+    search: String,
+    // lowercase id and cells of each task, what the quick search looks in
+    search_texts: Vec<String>,
+    selected: Option<usize>,
 
     wrapper_ref: yew::NodeRef,
+    container_ref: yew::NodeRef,
+    table_body_ref: yew::NodeRef,
+}
+
+pub enum GanttMsg {
+    /// The visible part of the chart changed.
+    Viewport(schemas::Viewport),
+    Zoom(schemas::ZoomEvent),
+    /// Zooms so the range fills the visible width of the chart.
+    ZoomToRange(NaiveDateTime, NaiveDateTime),
+    Bar(BarEvent),
+    /// Splits the task with the id at the date.
+    SplitTask(String, NaiveDateTime),
+    /// Collapses or expands the group with the key.
+    ToggleGroup(String),
+    TableResize(schemas::TableResize),
+    /// Sorts by the column, ascending, then descending, then not at all.
+    Sort(String),
+    /// Replaces the `filter` display option, `None` goes back to it.
+    Filter(Option<schemas::TaskFilter>),
+    Search(String),
+    DropRow(schemas::RowDrop),
+    /// Selects the task with the index.
+    Select(usize),
+    /// Makes the task with the id, or the selected one, part of the task above it.
+    Indent(Option<String>),
+    /// Moves the task with the id, or the selected one, out of its project.
+    Outdent(Option<String>),
+    /// A task edited in the table, already accepted.
+    EditTask(usize, Box<schemas::Task>),
 }
 
 impl Component for Gantt {
-    type Message = ();
+    type Message = GanttMsg;
     type Properties = schemas::GanttProps;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        let props = _ctx.props();
-        let fmt = props
+    fn create(ctx: &Context<Self>) -> Self {
+        let props = ctx.props();
+        let mut gantt = Gantt {
+            theme_class: format!("gantt-{}", INSTANCES.fetch_add(1, Ordering::Relaxed)),
+            tasks: props.tasks.clone().unwrap_or_default(),
+            ..Gantt::default()
+        };
+        gantt.layout(
+            props,
+            props.display_option.view_mode.clone().unwrap_or_default(),
+            props.style_option.column_width.unwrap_or(30.0),
+            None,
+        );
+        gantt
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        let min_width = props.style_option.min_column_width.unwrap_or(20.0);
+        let max_width = props.style_option.max_column_width.unwrap_or(300.0);
+        match msg {
+            GanttMsg::Viewport(viewport) => {
+                self.viewport = viewport;
+                let (rows, columns) = self.visible(0);
+                let covered = |range: &Range<usize>, visible: &Range<usize>| {
+                    range.start >= visible.start && range.end <= visible.end
+                };
+                if covered(&rows, &self.visible_rows) && covered(&columns, &self.visible_columns) {
+                    return false;
+                }
+                self.update_visible(props);
+            }
+            GanttMsg::Zoom(event) => {
+                let svg_width = self.grid_props.svg_width;
+                let x = if self.rtl { svg_width - event.x } else { event.x };
+                let date = x_date(x, &self.grid_props.dates, self.column_width);
+                let (view_mode, column_width) = zoom_level(
+                    &self.calendar_props.scale,
+                    self.column_width * event.factor,
+                    min_width,
+                    max_width,
+                    date,
+                );
+                if view_mode == self.view_mode && column_width == self.column_width {
+                    return false;
+                }
+
+                self.layout(props, view_mode, column_width, self.zoom_range);
+                // keep the date under the pointer
+                let x = task_x_coordinate(date, &self.grid_props.dates, column_width);
+                let x = if self.rtl { self.grid_props.svg_width - x } else { x };
+                self.scroll_x = (x - event.offset).max(0.0);
+                self.viewport.x = self.scroll_x;
+                self.update_visible(props);
+            }
+            GanttMsg::ZoomToRange(start, end) => {
+                let (start, end) = (start.min(end), start.max(end));
+                let width = self
+                    .container_ref
+                    .cast::<web_sys::Element>()
+                    .map(|it| it.client_width() as f64)
+                    .filter(|it| *it > 0.0)
+                    .unwrap_or(self.grid_props.svg_width);
+                let minutes = ((end - start).num_minutes() as f64).max(1.0);
+                let scale = &self.calendar_props.scale;
+                let candidates: Vec<ViewMode> = if ZOOM_LEVELS.contains(&scale.mode) {
+                    ZOOM_LEVELS.to_vec()
+                } else {
+                    vec![scale.mode.clone()]
+                };
+                // finest view mode which does not go below the minimal width
+                let (view_mode, column_width) = candidates
+                    .iter()
+                    .map(|mode| {
+                        let scale = Scale::new(mode.clone(), scale.options.clone());
+                        (mode.clone(), width * scale.column_minutes(start) / minutes)
+                    })
+                    .find(|(_, column_width)| *column_width >= min_width)
+                    .unwrap_or_else(|| {
+                        let mode = candidates.last().unwrap().clone();
+                        (mode, min_width)
+                    });
+
+                self.layout(props, view_mode, column_width.min(max_width), Some((start, end)));
+                let x_start = task_x_coordinate(start, &self.grid_props.dates, self.column_width);
+                let x_end = task_x_coordinate(end, &self.grid_props.dates, self.column_width);
+                self.scroll_x = if self.rtl {
+                    self.grid_props.svg_width - x_end
+                } else {
+                    x_start
+                };
+                self.viewport.x = self.scroll_x;
+                self.update_visible(props);
+            }
+            GanttMsg::Bar(BarEvent::SegmentDrag { task, segment, dx, done }) => {
+                let origin = match &self.drag {
+                    Some((t, s, origin)) if (*t, *s) == (task, segment) => origin.clone(),
+                    _ => match self.bar_segments(task).and_then(|it| it.get(segment)) {
+                        Some(origin) => origin.clone(),
+                        None => return false,
+                    },
+                };
+                if done {
+                    self.drag = None;
+                    return self.move_segment(props, task, segment, &origin, dx);
+                }
+
+                // segments keep their order, a dragged one stops at its neighbours
+                let others: Vec<BarSegment> = self
+                    .bar_segments(task)
+                    .into_iter()
+                    .flatten()
+                    .enumerate()
+                    .filter(|(i, _)| *i != segment)
+                    .map(|(_, it)| it.clone())
+                    .collect();
+                let lower = others
+                    .iter()
+                    .map(|it| it.x_2)
+                    .filter(|x| *x <= origin.x_1)
+                    .fold(0.0, f64::max);
+                let upper = others
+                    .iter()
+                    .map(|it| it.x_1)
+                    .filter(|x| *x >= origin.x_2)
+                    .fold(self.grid_props.svg_width, f64::min);
+                let dx = dx.clamp(lower - origin.x_1, (upper - origin.x_2).max(lower - origin.x_1));
+                if let Some(moved) = self
+                    .bar_props
+                    .tasks
+                    .get_mut(task)
+                    .and_then(|it| it.segments.as_mut())
+                    .and_then(|it| it.get_mut(segment))
+                {
+                    *moved = BarSegment {
+                        x_1: origin.x_1 + dx,
+                        x_2: origin.x_2 + dx,
+                        progress_x: origin.progress_x + dx,
+                        ..origin.clone()
+                    };
+                }
+                self.drag = Some((task, segment, origin));
+            }
+            GanttMsg::Bar(BarEvent::Split { task, x }) => {
+                let x = if self.rtl { self.grid_props.svg_width - x } else { x };
+                let date = x_date(x, &self.grid_props.dates, self.column_width);
+                return self.split(props, task, date);
+            }
+            GanttMsg::ToggleGroup(key) => {
+                if !self.collapsed_groups.remove(&key) {
+                    self.collapsed_groups.insert(key);
+                }
+                self.layout(props, self.view_mode.clone(), self.column_width, self.zoom_range);
+            }
+            GanttMsg::TableResize(resize) => {
+                match resize {
+                    schemas::TableResize::Column(id, width) => {
+                        self.column_widths.insert(id, width);
+                    }
+                    schemas::TableResize::Table(width) => self.table_width = Some(width),
+                }
+                self.layout(props, self.view_mode.clone(), self.column_width, self.zoom_range);
+                if let Some(on_table_resize) = &props.event_option.on_table_resize {
+                    on_table_resize.emit(schemas::TableWidths {
+                        columns: self.column_widths.clone(),
+                        table: self.table_width,
+                    });
+                }
+            }
+            GanttMsg::Sort(column) => {
+                self.sort = match self.sort.take() {
+                    Some((id, false)) if id == column => Some((id, true)),
+                    Some((id, true)) if id == column => None,
+                    _ => Some((column, false)),
+                };
+                self.layout(props, self.view_mode.clone(), self.column_width, self.zoom_range);
+            }
+            GanttMsg::Filter(filter) => {
+                self.filter = filter;
+                self.layout(props, self.view_mode.clone(), self.column_width, self.zoom_range);
+            }
+            GanttMsg::Search(search) => {
+                self.search = search;
+                // hidden tasks change the rows, dimmed ones only their look
+                match props.display_option.quick_search {
+                    Some(schemas::QuickSearch::Dim) => self.dim(),
+                    Some(schemas::QuickSearch::Hide) => self.layout(
+                        props,
+                        self.view_mode.clone(),
+                        self.column_width,
+                        self.zoom_range,
+                    ),
+                    None => return false,
+                }
+            }
+            GanttMsg::DropRow(drop) => {
+                // rows are dropped into projects only
+                let target = self.tasks.get(drop.target);
+                if drop.place == schemas::DropPlace::Into
+                    && target.is_none_or(|it| it.type_ != Some(TaskType::Project))
+                {
+                    return false;
+                }
+                return self.move_task(props, drop);
+            }
+            GanttMsg::Select(task) => self.select(Some(task)),
+            GanttMsg::Bar(BarEvent::Select { task }) => {
+                if self.selected == Some(task) {
+                    return false;
+                }
+                self.select(Some(task));
+            }
+            GanttMsg::Indent(id) => return self.indent(props, id, true),
+            GanttMsg::Outdent(id) => return self.indent(props, id, false),
+            GanttMsg::EditTask(task, changed) => self.apply_task(props, task, *changed),
+            GanttMsg::SplitTask(id, date) => {
+                let index = self.tasks.iter().position(|it| it.id.as_ref() == Some(&id));
+                return match index {
+                    Some(index) => self.split(props, index, date),
+                    None => false,
+                };
+            }
+        }
+        true
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        let props = ctx.props();
+        if props.tasks != old_props.tasks {
+            self.tasks = props.tasks.clone().unwrap_or_default();
+            self.drag = None;
+            self.selected = None;
+        }
+        if props.display_option.filter != old_props.display_option.filter {
+            self.filter = None;
+        }
+        // a new view mode or column width replaces the zoom
+        if props.display_option.view_mode != old_props.display_option.view_mode
+            || props.style_option.column_width != old_props.style_option.column_width
+        {
+            self.view_mode = props.display_option.view_mode.clone().unwrap_or_default();
+            self.column_width = props.style_option.column_width.unwrap_or(30.0);
+            self.zoom_range = None;
+        }
+        self.layout(props, self.view_mode.clone(), self.column_width, self.zoom_range);
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let on_zoom = ctx
+            .props()
             .display_option
-            .locale
+            .zoom
+            .unwrap_or(true)
+            .then(|| ctx.link().callback(GanttMsg::Zoom));
+        let table_props = self
+            .table_props
             .clone()
-            .unwrap_or_else(|| "%Y-%m-%d %H:%M:%S".to_owned());
-        let column_width = props.style_option.column_width.unwrap_or(30.0);
-        let row_height = props.style_option.row_height.unwrap_or(50.0);
-        let font_family = props.style_option.font_family.clone().unwrap_or_else(|| {
-            "Arial, Roboto, Oxygen, Ubuntu, Cantarell, Fira Sans, Droid Sans, Helvetica Neue"
-                .to_owned()
+            .on_group_toggle(Some(ctx.link().callback(GanttMsg::ToggleGroup)))
+            .on_resize(Some(ctx.link().callback(GanttMsg::TableResize)))
+            .on_task_edit(Some(ctx.link().callback(|(task, changed)| {
+                GanttMsg::EditTask(task, Box::new(changed))
+            })))
+            .validate_task_change(ctx.props().event_option.validate_task_change.clone())
+            .on_sort(Some(ctx.link().callback(GanttMsg::Sort)))
+            .on_row_drop(Some(ctx.link().callback(GanttMsg::DropRow)))
+            .on_select(Some(ctx.link().callback(GanttMsg::Select)));
+        // alt+shift+arrow indents or outdents the selected task, towards the end of the line
+        let rtl = self.rtl;
+        let on_key_down = ctx.link().batch_callback(move |e: yew::KeyboardEvent| {
+            let input = e.target_dyn_into::<web_sys::HtmlInputElement>().is_some();
+            if input || !e.alt_key() || !e.shift_key() {
+                return None;
+            }
+            let indent = match e.key().as_str() {
+                "ArrowRight" => !rtl,
+                "ArrowLeft" => rtl,
+                _ => return None,
+            };
+            e.prevent_default();
+            Some(if indent { GanttMsg::Indent(None) } else { GanttMsg::Outdent(None) })
+        });
+        let search = ctx.props().display_option.quick_search.map(|_| {
+            let oninput = ctx.link().callback(|e: yew::InputEvent| {
+                GanttMsg::Search(e.target_unchecked_into::<web_sys::HtmlInputElement>().value())
+            });
+            html! {
+                <div class="gantt-toolbar">
+                    <input
+                        class="gantt-search"
+                        type="search"
+                        placeholder="Search"
+                        value={self.search.clone()}
+                        oninput={oninput}
+                    />
+                </div>
+            }
         });
-        let font_size = props
-            .style_option
-            .font_size
+        html! {
+          <div>
+            <style>{self.theme.css(&format!(".{}", self.theme_class))}</style>
+            {search}
+            <div
+                class={format!("wrapper {}", self.theme_class)}
+                onkeydown={on_key_down}
+                tabIndex={0}
+                dir={if self.rtl { "rtl" } else { "ltr" }}
+                ref={self.wrapper_ref.clone()}
+            >
+              <table::Table ..table_props />
+              <svg_view::SvgView
+                    grid_props={self.grid_props.clone()}
+                    calendar_props={self.calendar_props.clone()}
+                    bar_props={self.bar_props.clone()}
+                    gantt_height={self.gantt_height}
+                    scroll_y={self.scroll_y}
+                    scroll_x={self.scroll_x}
+                    container_ref={self.container_ref.clone()}
+                    on_zoom={on_zoom}
+                    visible_rows={self.visible_rows.clone()}
+                    visible_columns={self.visible_columns.clone()}
+                    table_body_ref={self.table_body_ref.clone()}
+                    on_viewport={ctx.link().callback(GanttMsg::Viewport)}
+                    on_bar_event={ctx.link().callback(GanttMsg::Bar)}
+                    on_group_toggle={ctx.link().callback(GanttMsg::ToggleGroup)}
+              />
+          // <VerticalScroll
+          //     ganttFullHeight={ganttFullHeight}
+          //     ganttHeight={ganttHeight}
+          //     headerHeight={headerHeight}
+          //     scroll={scrollY}
+          //     onScroll={handleScrollY}
+          //     rtl={rtl}
+          // />
+              </div>
+                    // <HorizontalScroll
+                    //     svgWidth={svgWidth}
+                    //     taskListWidth={taskListWidth}
+                    //     scroll={scrollX}
+                    //     rtl={rtl}
+                    //     onScroll={handleScrollX}
+                    // />
+            </div>
+        }
+    }
+}
+
+impl Gantt {
+    /// Lays the chart out for the zoom state, `zoom_range` is shown even beyond the tasks.
+    fn layout(
+        &mut self,
+        props: &schemas::GanttProps,
+        view_mode: ViewMode,
+        column_width: f64,
+        zoom_range: Option<(NaiveDateTime, NaiveDateTime)>,
+    ) {
+        let fmt = date_format(&props.display_option);
+        let locale = props
+            .display_option
+            .locale
             .clone()
-            .unwrap_or_else(|| "14px".to_owned());
+            .filter(|it| !it.contains('%'))
+            .unwrap_or_else(|| "en".to_owned());
+        let row_height = props.style_option.row_height.unwrap_or(50.0);
+        let theme = Theme::from_style(&props.style_option);
+        let tz = display_time_zone(&props.display_option);
+        let display = props.display_option.table_date_format.as_deref();
+        let font_family = theme.font_family.clone();
+        let font_size = theme.font_size.clone();
         let header_height = props.style_option.header_height.unwrap_or(50.0);
         let bar_fill = props.style_option.bar_fill.unwrap_or(60.0);
         let task_height = (row_height * bar_fill) / 100.0;
-        let start_dates: NaiveDateTime = props
-            .tasks
-            .clone()
-            .unwrap_or_default()
+        let tasks = self.tasks.clone();
+        let task_dates: Vec<Vec<(NaiveDateTime, NaiveDateTime)>> =
+            tasks.iter().map(|it| it.segment_dates(&fmt, &tz)).collect();
+        let start_dates: NaiveDateTime = task_dates
             .iter()
-            .filter_map(|it| it.start.clone())
-            .filter_map(|it| match NaiveDateTime::parse_from_str(&it, &fmt) {
-                Ok(date) => Some(date),
-                Err(err) => {
-                    log::warn!("{}", err);
-                    None
-                }
-            })
+            .flatten()
+            .map(|it| it.0)
             .min()
-            .unwrap_or_else(|| Utc::now().naive_utc());
-        let end_dates: NaiveDateTime = props
-            .tasks
-            .clone()
-            .unwrap_or_default()
+            .unwrap_or_else(time_zone::now);
+        let end_dates: NaiveDateTime = task_dates
             .iter()
-            .filter_map(|it| it.end.clone())
-            .filter_map(|it| match NaiveDateTime::parse_from_str(&it, &fmt) {
-                Ok(date) => Some(date),
-                Err(err) => {
-                    log::warn!("{}", err);
-                    None
-                }
-            })
+            .flatten()
+            .map(|it| it.1)
             .max()
-            .unwrap_or_else(|| Utc::now().naive_utc());
-        let view_mode = props.display_option.view_mode.clone().unwrap_or_default();
-        // todo: ?
-        let dates_: Vec<NaiveDateTime> = seed_dates(start_dates, end_dates, view_mode.clone());
+            .unwrap_or_else(time_zone::now);
+        let (start_dates, end_dates) = match zoom_range {
+            Some((start, end)) => (start_dates.min(start), end_dates.max(end)),
+            None => (start_dates, end_dates),
+        };
+        let rtl = props.display_option.rtl.unwrap_or(false);
+        let week_start = props
+            .display_option
+            .week_start
+            .unwrap_or_else(|| Locale::get(&locale).week_start);
+        let scale = scale(&props.display_option, &view_mode, week_start, start_dates, &fmt, &tz);
+        let pre_steps = props.display_option.pre_steps_count.unwrap_or(1.0);
+        let pre_steps = pre_steps.max(0.0).round() as usize;
+        let dates_: Vec<NaiveDateTime> = seed_dates(start_dates, end_dates, &scale, pre_steps, &tz);
 
+        // calendar labels are written in the display time zone
         let date_setup = DateSetup {
             view_mode: Some(view_mode.clone()),
-            dates: Some(dates_.clone()),
+            dates: Some(dates_.iter().map(|it| tz.to_local(*it)).collect()),
+        };
+
+        let columns: Vec<schemas::TableColumn> = props
+            .display_option
+            .columns
+            .clone()
+            .unwrap_or_else(schemas::TableColumn::defaults)
+            .into_iter()
+            .map(|column| match self.column_widths.get(&column.id) {
+                Some(width) => column.width(format!("{}px", width)),
+                None => column,
+            })
+            .collect();
+        let sort = self.sort.as_ref().and_then(|(id, descending)| {
+            Some((columns.iter().find(|it| it.id == *id)?, *descending))
+        });
+        let mut order = sorted(&tasks, &task_dates, sort, &fmt, &tz);
+        if let Some(filter) = self.filter.as_ref().or(props.display_option.filter.as_ref()) {
+            let shown = filtered(filter, &tasks, &task_dates);
+            order.retain(|i| shown[*i]);
+        }
+        let search_texts: Vec<String> = match props.display_option.quick_search {
+            Some(_) => tasks
+                .iter()
+                .map(|task| search_text(task, &columns, &fmt, &tz, display))
+                .collect(),
+            None => Vec::new(),
         };
+        if props.display_option.quick_search == Some(schemas::QuickSearch::Hide) {
+            let query = self.search.trim().to_lowercase();
+            order.retain(|i| search_texts[*i].contains(&query));
+        }
+        let (mut rows, task_rows) = rows(
+            &tasks,
+            &order,
+            &task_dates,
+            props.display_option.group_by.as_deref(),
+            props.display_option.compact.unwrap_or(false),
+            &self.collapsed_groups,
+        );
+        let svg_width = (dates_.len() as f64) * column_width;
+        for row in rows.iter_mut() {
+            let Row::Group(group) = row else {
+                continue;
+            };
+            let dates = group.tasks.iter().flat_map(|i| task_dates[*i].iter());
+            let start = dates.clone().map(|it| it.0).min();
+            let end = dates.map(|it| it.1).max();
+            let (Some(start), Some(end)) = (start, end) else {
+                continue;
+            };
+            let x_start = task_x_coordinate(start, &dates_, column_width);
+            let x_end = task_x_coordinate(end, &dates_, column_width);
+            (group.x_1, group.x_2) = if rtl {
+                (svg_width - x_end, svg_width - x_start)
+            } else {
+                (x_start, x_end)
+            };
+            let style = display.unwrap_or(&fmt);
+            group.start = time_zone::display_date(start, style, &tz);
+            group.end = time_zone::display_date(end, style, &tz);
+        }
 
         let grid_props_ = schemas::GridProps::default()
-            .tasks(props.tasks.clone().unwrap())
+            .tasks(tasks.clone())
+            .rows(rows.clone())
             .row_height(props.style_option.row_height.unwrap_or(50.0))
             .column_width(column_width)
-            .dates(date_setup.dates.clone().unwrap())
-            .rtl(false)
+            .dates(dates_.clone())
+            .rtl(rtl)
             .svg_width((dates_.len() as f64) * column_width)
-            .today_color(
+            .today_color(var("--gantt-today-color", &theme.today_color))
+            .markers(
                 props
-                    .style_option
-                    .today_color
+                    .display_option
+                    .markers
                     .clone()
-                    .unwrap_or_else(|| "rgba(252, 248, 227, 0.5)".to_owned()),
+                    .unwrap_or_default()
+                    .iter()
+                    .filter_map(|it| grid_marker(it, &fmt, &tz, &dates_, column_width, &theme, rtl))
+                    .collect::<Vec<schemas::GridMarker>>(),
             );
 
         let calendar_props_ = schemas::CalendarProps::default()
@@ -108,248 +595,1141 @@ impl Component for Gantt {
             .font_family(font_family.clone())
             .font_size(font_size.clone())
             .header_height(header_height)
-            .locale(fmt.clone()) // todo
-            .rtl(false)
-            .view_mode(view_mode);
+            .locale(locale.clone())
+            .labels(props.display_option.calendar_labels.clone().unwrap_or_default())
+            .formatter(props.display_option.calendar_formatter.clone())
+            .scale(scale)
+            .rtl(rtl)
+            .view_mode(view_mode.clone());
 
-        // todo!!
-        let tasks = props.tasks.clone().unwrap_or_default();
-        let bar_tasks: Vec<BarTask> = tasks
+        let bars: Vec<BarTask> = tasks
             .iter()
             .enumerate()
             .map(|(i, it)| {
-                let childrens: Vec<BarTask> = tasks
-                    .iter()
-                    .filter_map(|el| {
-                        el.dependencies
-                            .clone()
-                            .unwrap_or_default()
-                            .iter()
-                            .find(|id| &it.id.clone().unwrap() == *id)
-                            .map(|_| el)
-                    })
-                    .map(|task| {
-                        let index = tasks
-                            .iter()
-                            .position(|el| el.id.to_owned().unwrap() == task.id.to_owned().unwrap())
-                            .unwrap();
-                        bar_task(
-                            task.clone(),
-                            fmt.clone(),
-                            &dates_,
-                            column_width,
-                            props,
-                            task_height,
-                            index,
-                            row_height,
-                        )
-                    })
-                    .collect();
-
                 bar_task(
                     it.clone(),
                     fmt.clone(),
                     &dates_,
                     column_width,
                     props,
+                    &theme,
+                    &tz,
                     task_height,
-                    i,
+                    task_rows[i],
                     row_height,
                 )
-                .bar_children(childrens)
             })
             .collect();
+        // tasks of collapsed groups have no row of their own and no arrows
+        let mut shown = vec![false; tasks.len()];
+        for row in &rows {
+            match row {
+                Row::Group(_) => {}
+                Row::Task(i) => shown[*i] = true,
+                Row::Lane(lane) => lane.tasks.iter().for_each(|i| shown[*i] = true),
+            }
+        }
+        let index = task_index(&tasks);
+        // dependent tasks of each task, the ends of its arrows
+        let mut successors: Vec<Vec<usize>> = vec![Vec::new(); tasks.len()];
+        for (j, task) in tasks.iter().enumerate() {
+            for id in task.dependencies.iter().flatten() {
+                if let Some(&i) = index.get(id.as_str()) {
+                    if shown[j] && !successors[i].contains(&j) {
+                        successors[i].push(j);
+                    }
+                }
+            }
+        }
+        let bar_tasks: Vec<BarTask> = bars
+            .iter()
+            .zip(&successors)
+            .map(|(bar, successors)| {
+                let children: Vec<BarTask> = successors.iter().map(|j| bars[*j].clone()).collect();
+                bar.clone().bar_children(children)
+            })
+            .collect();
+        let (wbs, levels) = outline(&tasks, &sorted(&tasks, &task_dates, None, &fmt, &tz));
         let bar_props_ = schemas::TaskGanttContentProps::default()
             .tasks(bar_tasks)
             .dates(dates_.clone())
             .gantt_event(schemas::GanttEvent::default())
-            .selected_task(schemas::BarTask::default()) // todo!
             .row_height(row_height)
             .time_step(props.event_option.time_step.unwrap_or(300000.0))
             .svg(yew::NodeRef::default()) // todo!
             .svg_width((dates_.len() as f64) * column_width)
             .task_height(task_height)
-            .arrow_color(
-                props
-                    .style_option
-                    .arrow_color
-                    .clone()
-                    .unwrap_or_else(|| "grey".to_owned()),
-            )
+            .arrow_color(var("--gantt-arrow-color", &theme.arrow_color))
             .arrow_indent(props.style_option.arrow_indent.unwrap_or(20.0))
             .column_width(column_width)
-            .rtl(false)
+            .rtl(rtl)
             .font_family(font_family.clone())
-            .font_size(font_size.clone());
-        
+            .font_size(font_size.clone())
+            .tooltip_content(props.style_option.tooltip_content.clone());
+
         let table_props_ = schemas::TableProps::default()
             .tasks(tasks)
             .row_height(row_height)
             .row_width(props.style_option.list_cell_width.clone().unwrap_or_else(|| "155px".to_owned()))
             .font_family(font_family)
             .font_size(font_size)
-            .locale(fmt)
+            .locale(locale)
+            .rtl(rtl)
+            .rows(rows)
+            .columns(columns)
+            .sort(self.sort.clone())
+            .wbs(wbs)
+            .levels(levels)
+            .header_content(props.style_option.task_list_header.clone())
+            .row_content(props.style_option.task_list_table.clone())
+            .table_width(self.table_width.or(props.style_option.table_width))
+            .date_format(fmt.clone())
+            .display_date_format(display.map(str::to_owned))
+            .time_zone(tz.clone())
+            .body_ref(self.table_body_ref.clone())
+            .scroll_ref(self.container_ref.clone())
             .header_height(header_height)
             // todo!!
             //.scroll_y() 
-            // .selected_task_id(selected_task_id)
             // .set_selected_task(set_selected_task)
             // .on_expander_click(on_expander_click)
             .gantt_height(props.style_option.gantt_height.unwrap_or(0.0));
 
-        Gantt {
-            grid_props: grid_props_,
-            calendar_props: calendar_props_,
-            bar_props: bar_props_,
-            table_props: table_props_,
-            gantt_height: props.style_option.gantt_height.unwrap_or(0.0),
-            scroll_y: 0.0,
-            scroll_x: -1.0,
-            wrapper_ref: yew::NodeRef::default(), // todo!
+        self.grid_props = grid_props_;
+        self.calendar_props = calendar_props_;
+        self.bar_props = bar_props_;
+        self.table_props = table_props_;
+        self.gantt_height = props.style_option.gantt_height.unwrap_or(0.0);
+        self.scroll_y = 0.0;
+        self.scroll_x = -1.0;
+        self.rtl = rtl;
+        self.theme = theme;
+        self.view_mode = view_mode;
+        self.column_width = column_width;
+        self.zoom_range = zoom_range;
+        self.search_texts = search_texts;
+        self.select(self.selected);
+        if props.display_option.quick_search == Some(schemas::QuickSearch::Dim) {
+            self.dim();
         }
+        self.update_visible(props);
     }
 
-    fn view(&self, _ctx: &Context<Self>) -> Html {
-        html! {
-          <div>
-            <div
-                class="wrapper"
-                // onKeyDown={handleKeyDown} TODO:!!
-                tabIndex={0}
-                ref={self.wrapper_ref.clone()}  
-            >
-              <table::Table ..self.table_props.clone() /> 
-              <svg_view::SvgView
-                    grid_props={self.grid_props.clone()}
-                    calendar_props={self.calendar_props.clone()}
-                    bar_props={self.bar_props.clone()}
-                    gantt_height={self.gantt_height}
-                    scroll_y={self.scroll_y}
-                    scroll_x={self.scroll_x}
-              />
-          // <Tooltip
-          //     arrowIndent={arrowIndent}
-          //     rowHeight={rowHeight}
-          //     svgContainerHeight={svgContainerHeight}
-          //     svgContainerWidth={svgContainerWidth}
-          //     fontFamily={fontFamily}
-          //     fontSize={fontSize}
-          //     scrollX={scrollX}
-          //     scrollY={scrollY}
-          //     task={ganttEvent.changedTask}
-          //     headerHeight={headerHeight}
-          //     taskListWidth={taskListWidth}
-          //     TooltipContent={TooltipContent}
-          //     rtl={rtl}
-          //     svgWidth={svgWidth}
-          // />
+    /// Marks the task with the index as selected on the chart and in the table.
+    fn select(&mut self, task: Option<usize>) {
+        self.selected = task;
+        let selected = task.and_then(|i| self.bar_props.tasks.get(i)).cloned();
+        self.table_props.selected_task_id = selected.as_ref().and_then(|it| it.task.id.clone());
+        self.bar_props.selected_task = selected.unwrap_or_default();
+    }
 
-          // <VerticalScroll
-          //     ganttFullHeight={ganttFullHeight}
-          //     ganttHeight={ganttHeight}
-          //     headerHeight={headerHeight}
-          //     scroll={scrollY}
-          //     onScroll={handleScrollY}
-          //     rtl={rtl}
-          // />
-              </div>
-                    // <HorizontalScroll
-                    //     svgWidth={svgWidth}
-                    //     taskListWidth={taskListWidth}
-                    //     scroll={scrollX}
-                    //     rtl={rtl}
-                    //     onScroll={handleScrollX}
-                    // />
-            </div>
+    /// Dims the tasks not matching the quick search.
+    fn dim(&mut self) {
+        let query = self.search.trim().to_lowercase();
+        let dimmed: HashSet<usize> = (0..self.search_texts.len())
+            .filter(|i| !self.search_texts[*i].contains(&query))
+            .collect();
+        for (i, bar) in self.bar_props.tasks.iter_mut().enumerate() {
+            bar.dimmed = Some(dimmed.contains(&i));
+        }
+        self.table_props.dimmed = dimmed;
+    }
+
+    /// Rows and columns of the viewport, widened by `overscan` on every side.
+    fn visible(&self, overscan: usize) -> (Range<usize>, Range<usize>) {
+        // before the first measurement assume a large screen
+        let viewport = match self.viewport {
+            schemas::Viewport { width, height, .. } if width > 0.0 || height > 0.0 => self.viewport,
+            viewport => schemas::Viewport { width: 1920.0, height: 1080.0, ..viewport },
+        };
+        let range = |start: f64, length: f64, size: f64, count: usize| {
+            let first = (start / size).floor().max(0.0) as usize;
+            let last = ((start + length) / size).ceil().max(0.0) as usize;
+            first.saturating_sub(overscan).min(count)..(last + overscan).min(count)
+        };
+        let mut rows = range(
+            viewport.y,
+            viewport.height,
+            self.bar_props.row_height,
+            self.grid_props.rows.len(),
+        );
+        // striped rows alternate by position, keep the first one even
+        rows.start -= rows.start % 2;
+        // chart x are mirrored in rtl, columns are counted from the start date
+        let x = if self.rtl {
+            self.grid_props.svg_width - viewport.x - viewport.width
+        } else {
+            viewport.x
+        };
+        let columns = range(
+            x,
+            viewport.width,
+            self.column_width,
+            self.grid_props.dates.len(),
+        );
+        (rows, columns)
+    }
+
+    fn update_visible(&mut self, props: &schemas::GanttProps) {
+        let (rows, columns) = self.visible(props.display_option.overscan.unwrap_or(10));
+        self.table_props.visible_rows = rows.clone();
+        self.calendar_props.visible_columns = columns.clone();
+        self.visible_rows = rows;
+        self.visible_columns = columns;
+    }
+
+    fn bar_segments(&self, task: usize) -> Option<&Vec<BarSegment>> {
+        self.bar_props.tasks.get(task)?.segments.as_ref()
+    }
+
+    /// Moves a segment dragged `dx` from its `origin` layout, in whole time steps and not past
+    /// its neighbours.
+    fn move_segment(
+        &mut self,
+        props: &schemas::GanttProps,
+        task: usize,
+        segment: usize,
+        origin: &BarSegment,
+        dx: f64,
+    ) -> bool {
+        let fmt = date_format(&props.display_option);
+        let tz = display_time_zone(&props.display_option);
+        let Some(mut segments) = self.tasks.get(task).map(|it| it.segment_dates(&fmt, &tz)) else {
+            return false;
+        };
+        let Some((start, end)) = segments.get(segment).copied() else {
+            return false;
+        };
+        // the start is on the right side in rtl
+        let x = if self.rtl {
+            self.grid_props.svg_width - origin.x_2 - dx
+        } else {
+            origin.x_1 + dx
+        };
+        let mut shift = time_step(
+            x_date(x, &self.grid_props.dates, self.column_width) - start,
+            props.event_option.time_step.unwrap_or(300000.0),
+        );
+        if let Some((_, previous)) = segment.checked_sub(1).map(|i| segments[i]) {
+            shift = shift.max(previous - start);
+        }
+        if let Some((next, _)) = segments.get(segment + 1) {
+            shift = shift.min(*next - end);
+        }
+        segments[segment] = (start + shift, end + shift);
+        self.update_task(props, task, &segments);
+        true
+    }
+
+    /// Splits the work segment of the task containing `date`, at the nearest time step.
+    fn split(&mut self, props: &schemas::GanttProps, task: usize, date: NaiveDateTime) -> bool {
+        let fmt = date_format(&props.display_option);
+        let tz = display_time_zone(&props.display_option);
+        let Some(mut segments) = self
+            .tasks
+            .get(task)
+            .filter(|it| it.type_ == Some(TaskType::Task))
+            .map(|it| it.segment_dates(&fmt, &tz))
+        else {
+            return false;
+        };
+        let Some(i) = segments.iter().position(|(start, end)| *start < date && date < *end) else {
+            return false;
+        };
+        let (start, end) = segments[i];
+        let step = props.event_option.time_step.unwrap_or(300000.0);
+        let date = start + time_step(date - start, step);
+        if date <= start || date >= end {
+            return false;
+        }
+        segments[i] = (start, date);
+        segments.insert(i + 1, (date, end));
+        self.update_task(props, task, &segments);
+        true
+    }
+
+    /// Writes the segments into the task if the change is accepted, otherwise lays the chart out
+    /// again to drop the preview.
+    fn update_task(
+        &mut self,
+        props: &schemas::GanttProps,
+        task: usize,
+        segments: &[(NaiveDateTime, NaiveDateTime)],
+    ) {
+        let fmt = date_format(&props.display_option);
+        let tz = display_time_zone(&props.display_option);
+        let Some(original) = self.tasks.get(task) else {
+            return;
+        };
+        let mut changed = original.clone();
+        changed.set_segment_dates(segments, &fmt, &tz);
+        let validate = &props.event_option.validate_task_change;
+        match check_task_change(validate, original, &changed, &fmt, &tz) {
+            Ok(()) => self.apply_task(props, task, changed),
+            Err(err) => {
+                log::warn!("{}", err);
+                self.layout(props, self.view_mode.clone(), self.column_width, self.zoom_range);
+            }
+        }
+    }
+
+    /// Moves the task next to or into the target, it becomes part of the project of its new
+    /// place. `displayOrder` numbers all tasks in their new order.
+    fn move_task(&mut self, props: &schemas::GanttProps, drop: schemas::RowDrop) -> bool {
+        let schemas::RowDrop { task, target, place } = drop;
+        let tasks = &self.tasks;
+        if task == target || task >= tasks.len() || target >= tasks.len() {
+            return false;
+        }
+        // a project does not go into itself
+        if is_part_of(tasks, target, task) {
+            return false;
+        }
+        let project = match place {
+            schemas::DropPlace::Into => tasks[target].id.clone(),
+            _ => tasks[target].project.clone().filter(|it| tasks[target].id.as_ref() != Some(it)),
+        };
+        let fmt = date_format(&props.display_option);
+        let tz = display_time_zone(&props.display_option);
+        let task_dates: Vec<Vec<(NaiveDateTime, NaiveDateTime)>> =
+            tasks.iter().map(|it| it.segment_dates(&fmt, &tz)).collect();
+        let mut order = sorted(tasks, &task_dates, None, &fmt, &tz);
+        order.retain(|it| *it != task);
+        let Some(position) = order.iter().position(|it| *it == target) else {
+            return false;
+        };
+        let position = match place {
+            schemas::DropPlace::Before => position,
+            schemas::DropPlace::After => position + 1,
+            // after the last task of the project
+            schemas::DropPlace::Into => {
+                position
+                    + 1
+                    + order[position + 1..]
+                        .iter()
+                        .take_while(|it| is_part_of(tasks, **it, target))
+                        .count()
+            }
+        };
+        order.insert(position, task);
+
+        let mut changed = tasks[task].clone();
+        changed.project = project;
+        let validate = &props.event_option.validate_task_change;
+        if let Err(err) = check_task_change(validate, &tasks[task], &changed, &fmt, &tz) {
+            log::warn!("{}", err);
+            return false;
+        }
+        self.tasks[task] = changed;
+        for (i, task) in order.into_iter().enumerate() {
+            self.tasks[task].display_order = Some((i + 1) as f64);
+        }
+        self.layout(props, self.view_mode.clone(), self.column_width, self.zoom_range);
+        if let Some(on_reorder) = &props.event_option.on_reorder {
+            on_reorder.emit(self.tasks.clone());
+        }
+        true
+    }
+
+    /// Puts the task into its previous sibling, or for `indent == false` next to its project.
+    /// Siblings follow `displayOrder` whatever the table is sorted by, and a plain task indented
+    /// into becomes a project like a summary task in MS Project.
+    fn indent(&mut self, props: &schemas::GanttProps, id: Option<String>, indent: bool) -> bool {
+        let task = match id {
+            Some(id) => self.tasks.iter().position(|it| it.id.as_ref() == Some(&id)),
+            None => self.selected,
+        };
+        let Some(task) = task.filter(|it| *it < self.tasks.len()) else {
+            return false;
+        };
+        let project = parent(&self.tasks, task);
+        let target = if indent {
+            let fmt = date_format(&props.display_option);
+            let tz = display_time_zone(&props.display_option);
+            let task_dates: Vec<Vec<(NaiveDateTime, NaiveDateTime)>> =
+                self.tasks.iter().map(|it| it.segment_dates(&fmt, &tz)).collect();
+            let order = sorted(&self.tasks, &task_dates, None, &fmt, &tz);
+            order
+                .iter()
+                .take_while(|it| **it != task)
+                .filter(|it| parent(&self.tasks, **it) == project)
+                .last()
+                .copied()
+        } else {
+            project
+        };
+        let Some(target) = target else {
+            return false;
+        };
+        if !indent {
+            let place = schemas::DropPlace::After;
+            return self.move_task(props, schemas::RowDrop { task, target, place });
+        }
+        let original = self.tasks[target].clone();
+        if original.type_ != Some(TaskType::Project) {
+            let mut changed = original.clone();
+            changed.type_ = Some(TaskType::Project);
+            let fmt = date_format(&props.display_option);
+            let tz = display_time_zone(&props.display_option);
+            let validate = &props.event_option.validate_task_change;
+            if let Err(err) = check_task_change(validate, &original, &changed, &fmt, &tz) {
+                log::warn!("{}", err);
+                return false;
+            }
+            self.tasks[target] = changed;
+        }
+        let place = schemas::DropPlace::Into;
+        let moved = self.move_task(props, schemas::RowDrop { task, target, place });
+        if !moved {
+            self.tasks[target] = original;
+        }
+        moved
+    }
+
+    /// Replaces an accepted task change, lays the chart out again and reports the change.
+    fn apply_task(&mut self, props: &schemas::GanttProps, task: usize, changed: schemas::Task) {
+        let Some(original) = self.tasks.get_mut(task) else {
+            return;
+        };
+        *original = changed.clone();
+        self.layout(props, self.view_mode.clone(), self.column_width, self.zoom_range);
+        if let Some(on_task_change) = &props.event_option.on_task_change {
+            on_task_change.emit(changed);
+        }
+    }
+}
+
+/// Order of the tasks by `displayOrder`, or sorted by a column, descending for `true`, values
+/// missing last either way. Tasks of a project stay right under it, ordered among themselves.
+fn sorted(
+    tasks: &[schemas::Task],
+    task_dates: &[Vec<(NaiveDateTime, NaiveDateTime)>],
+    sort: Option<(&schemas::TableColumn, bool)>,
+    fmt: &str,
+    tz: &DisplayTimeZone,
+) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..tasks.len()).collect();
+    let keys: Vec<Option<SortKey>> = match sort {
+        // outline numbers follow `displayOrder`
+        Some((column, _)) if column.id != "wbs" || column.value.is_some() => tasks
+            .iter()
+            .zip(task_dates)
+            .map(|(task, dates)| sort_key(column, task, dates, fmt, tz))
+            .collect(),
+        _ => tasks.iter().map(|it| it.display_order.map(SortKey::Number)).collect(),
+    };
+    let descending = sort.is_some_and(|(_, descending)| descending);
+    order.sort_by(|a, b| match (&keys[*a], &keys[*b]) {
+        (Some(a), Some(b)) => {
+            let ordering = a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal);
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        }
+        (a, b) => a.is_none().cmp(&b.is_none()),
+    });
+
+    let parents = parents(tasks, &task_index(tasks));
+    if parents.iter().all(Option::is_none) {
+        return order;
+    }
+    let mut children = vec![Vec::new(); tasks.len()];
+    for &i in &order {
+        if let Some(parent) = parents[i] {
+            children[parent].push(i);
+        }
+    }
+    fn visit(i: usize, children: &[Vec<usize>], visited: &mut [bool], tree: &mut Vec<usize>) {
+        if std::mem::replace(&mut visited[i], true) {
+            return;
+        }
+        tree.push(i);
+        for child in &children[i] {
+            visit(*child, children, visited, tree);
+        }
+    }
+    let mut visited = vec![false; tasks.len()];
+    let mut tree = Vec::with_capacity(tasks.len());
+    for &i in order.iter().filter(|i| parents[**i].is_none()) {
+        visit(i, &children, &mut visited, &mut tree);
+    }
+    // projects containing each other have no root
+    for &i in &order {
+        visit(i, &children, &mut visited, &mut tree);
+    }
+    tree
+}
+
+/// Outline number like `1.2.3` and depth of each task in the project hierarchy, numbered in
+/// `order`.
+fn outline(tasks: &[schemas::Task], order: &[usize]) -> (Vec<String>, Vec<usize>) {
+    let mut wbs = vec![String::new(); tasks.len()];
+    let mut levels = vec![0; tasks.len()];
+    // tasks numbered so far under each task, and at the top
+    let mut counts = vec![0; tasks.len()];
+    let mut top = 0;
+    let parents = parents(tasks, &task_index(tasks));
+    for &i in order {
+        match parents[i].filter(|it| !wbs[*it].is_empty()) {
+            Some(parent) => {
+                counts[parent] += 1;
+                wbs[i] = format!("{}.{}", wbs[parent], counts[parent]);
+                levels[i] = levels[parent] + 1;
+            }
+            None => {
+                top += 1;
+                wbs[i] = top.to_string();
+            }
+        }
+    }
+    (wbs, levels)
+}
+
+/// Index of each task by id, the first task of ids given twice.
+fn task_index(tasks: &[schemas::Task]) -> HashMap<&str, usize> {
+    let mut index = HashMap::with_capacity(tasks.len());
+    for (i, task) in tasks.iter().enumerate() {
+        if let Some(id) = &task.id {
+            index.entry(id.as_str()).or_insert(i);
+        }
+    }
+    index
+}
+
+/// Project task each task is part of, like [`parent`] for all of them at once.
+fn parents(tasks: &[schemas::Task], index: &HashMap<&str, usize>) -> Vec<Option<usize>> {
+    tasks
+        .iter()
+        .enumerate()
+        .map(|(i, task)| index.get(task.project.as_deref()?).copied().filter(|it| *it != i))
+        .collect()
+}
+
+/// Project task the task is part of.
+fn parent(tasks: &[schemas::Task], task: usize) -> Option<usize> {
+    let project = tasks[task].project.as_ref()?;
+    tasks.iter().position(|it| it.id.as_ref() == Some(project)).filter(|it| *it != task)
+}
+
+/// Whether `task` is part of `project`, directly or through other projects.
+fn is_part_of(tasks: &[schemas::Task], task: usize, project: usize) -> bool {
+    let mut current = task;
+    // projects containing each other end the walk too
+    for _ in 0..tasks.len() {
+        match parent(tasks, current) {
+            Some(parent) if parent == project => return true,
+            Some(parent) => current = parent,
+            None => return false,
+        }
+    }
+    false
+}
+
+#[derive(PartialEq, PartialOrd)]
+enum SortKey {
+    Number(f64),
+    Text(String),
+}
+
+fn sort_key(
+    column: &schemas::TableColumn,
+    task: &schemas::Task,
+    dates: &[(NaiveDateTime, NaiveDateTime)],
+    fmt: &str,
+    tz: &DisplayTimeZone,
+) -> Option<SortKey> {
+    let timestamp = |date: NaiveDateTime| SortKey::Number(date.and_utc().timestamp() as f64);
+    match column.id.as_str() {
+        _ if column.value.is_some() => {}
+        "start" => return dates.first().map(|it| timestamp(it.0)),
+        "end" => return dates.last().map(|it| timestamp(it.1)),
+        "duration" => return Some(SortKey::Number(task.work_time(fmt, tz).num_minutes() as f64)),
+        "progress" => return task.progress.map(SortKey::Number),
+        _ => {}
+    }
+    let text = table::cell_value(column, task, fmt, tz, None);
+    (!text.is_empty()).then(|| SortKey::Text(text.to_lowercase()))
+}
+
+/// Whether each task meets the filter.
+fn filtered(
+    filter: &schemas::TaskFilter,
+    tasks: &[schemas::Task],
+    task_dates: &[Vec<(NaiveDateTime, NaiveDateTime)>],
+) -> Vec<bool> {
+    let now = time_zone::now();
+    let critical = filter.critical.map(|it| (it, critical(tasks, task_dates)));
+    tasks
+        .iter()
+        .enumerate()
+        .map(|(i, task)| {
+            let late = task_dates[i].last().is_some_and(|it| it.1 < now)
+                && task.progress.unwrap_or_default() < 100.0;
+            filter.late.is_none_or(|it| it == late)
+                && critical.as_ref().is_none_or(|(it, critical)| critical.contains(&i) == *it)
+                && filter.assigned_to.as_ref().is_none_or(|it| task.assignee.as_ref() == Some(it))
+                && filter.predicate.as_ref().is_none_or(|it| it.emit(task.clone()))
+        })
+        .collect()
+}
+
+/// Tasks on the critical path, the ones without total float. A forward pass over the
+/// dependencies starts each task at its own start or once its dependencies end, whichever is
+/// later. A backward pass from the end of the schedule then gives the latest each task could end
+/// without delaying it. Dependencies in a cycle are ignored where the cycle closes.
+fn critical(
+    tasks: &[schemas::Task],
+    task_dates: &[Vec<(NaiveDateTime, NaiveDateTime)>],
+) -> HashSet<usize> {
+    let index = task_index(tasks);
+    let spans: Vec<_> =
+        task_dates.iter().map(|dates| Some((dates.first()?.0, dates.last()?.1))).collect();
+    let mut dependencies = vec![Vec::new(); tasks.len()];
+    let mut successors = vec![Vec::new(); tasks.len()];
+    for (i, task) in tasks.iter().enumerate() {
+        for id in task.dependencies.iter().flatten() {
+            match index.get(id.as_str()) {
+                Some(&j) if j != i && spans[i].is_some() && spans[j].is_some() => {
+                    dependencies[i].push(j);
+                    successors[j].push(i);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    // dependencies first, tasks left in a cycle after the others
+    let mut waiting: Vec<usize> = dependencies.iter().map(Vec::len).collect();
+    let mut order: Vec<usize> = (0..tasks.len()).filter(|i| waiting[*i] == 0).collect();
+    let mut next = 0;
+    while next < order.len() {
+        for &j in &successors[order[next]] {
+            waiting[j] -= 1;
+            if waiting[j] == 0 {
+                order.push(j);
+            }
+        }
+        next += 1;
+    }
+    order.extend((0..tasks.len()).filter(|i| waiting[*i] > 0));
+
+    let mut done = vec![false; tasks.len()];
+    let mut early_end = vec![NaiveDateTime::MIN; tasks.len()];
+    for &i in &order {
+        let Some((start, end)) = spans[i] else {
+            continue;
+        };
+        let after = dependencies[i].iter().filter(|j| done[**j]).map(|j| early_end[*j]).max();
+        early_end[i] = after.max(Some(start)).unwrap_or(start) + (end - start);
+        done[i] = true;
+    }
+    let Some(end) = (0..tasks.len()).filter(|i| spans[*i].is_some()).map(|i| early_end[i]).max()
+    else {
+        return HashSet::new();
+    };
+
+    let mut done = vec![false; tasks.len()];
+    let mut late_start = vec![NaiveDateTime::MAX; tasks.len()];
+    let mut critical = HashSet::new();
+    for &i in order.iter().rev() {
+        let Some((start, finish)) = spans[i] else {
+            continue;
+        };
+        let late_end = successors[i]
+            .iter()
+            .filter(|j| done[**j])
+            .map(|j| late_start[*j])
+            .fold(end, NaiveDateTime::min);
+        late_start[i] = late_end - (finish - start);
+        done[i] = true;
+        if late_end <= early_end[i] {
+            critical.insert(i);
+        }
+    }
+    critical
+}
+
+/// Id and cells of the task in lowercase, one per line, for the quick search.
+fn search_text(
+    task: &schemas::Task,
+    columns: &[schemas::TableColumn],
+    fmt: &str,
+    tz: &DisplayTimeZone,
+    display: Option<&str>,
+) -> String {
+    let cells = columns.iter().map(|column| table::cell_value(column, task, fmt, tz, display));
+    let text: Vec<String> = task.id.iter().cloned().chain(cells).collect();
+    text.join("\n").to_lowercase()
+}
+
+/// Rows of the tasks in `order`, tasks left out get none. With `group_by` the tasks are grouped
+/// under header rows, groups in order of their first task, tasks without a value last. `compact`
+/// packs each group, by default each project, into lanes instead. Also returns the row of each
+/// task, none for tasks left out or in a collapsed group.
+fn rows(
+    tasks: &[schemas::Task],
+    order: &[usize],
+    task_dates: &[Vec<(NaiveDateTime, NaiveDateTime)>],
+    group_by: Option<&str>,
+    compact: bool,
+    collapsed: &HashSet<String>,
+) -> (Vec<Row>, Vec<Option<usize>>) {
+    let group_by = match (group_by, compact) {
+        (Some(group_by), _) => group_by,
+        (None, true) => "project",
+        (None, false) => {
+            let mut task_rows = vec![None; tasks.len()];
+            for (row, i) in order.iter().enumerate() {
+                task_rows[*i] = Some(row);
+            }
+            return (order.iter().copied().map(Row::Task).collect(), task_rows);
+        }
+    };
+    let mut groups: Vec<(Option<String>, Vec<usize>)> = Vec::new();
+    let mut group_index: HashMap<Option<String>, usize> = HashMap::new();
+    for &i in order {
+        let key = tasks[i].field(group_by);
+        match group_index.get(&key) {
+            Some(group) => groups[*group].1.push(i),
+            None => {
+                group_index.insert(key.clone(), groups.len());
+                groups.push((key, vec![i]));
+            }
+        }
+    }
+    groups.sort_by_key(|(key, _)| key.is_none());
+    let index = task_index(tasks);
+
+    let mut rows = Vec::new();
+    let mut task_rows = vec![None; tasks.len()];
+    for (key, members) in groups {
+        let key = key.unwrap_or_default();
+        // projects are labelled by their name
+        let label = index
+            .get(key.as_str())
+            .filter(|_| group_by == "project")
+            .and_then(|it| tasks[*it].name.clone())
+            .unwrap_or_else(|| if key.is_empty() { "—".to_owned() } else { key.clone() });
+        if compact {
+            let lanes = lanes(&members, task_dates);
+            let count = lanes.len();
+            for (lane, lane_tasks) in lanes.into_iter().enumerate() {
+                for i in &lane_tasks {
+                    task_rows[*i] = Some(rows.len());
+                }
+                rows.push(Row::Lane(schemas::LaneRow {
+                    key: key.clone(),
+                    label: label.clone(),
+                    lane,
+                    lanes: count,
+                    tasks: lane_tasks,
+                    count: members.len(),
+                }));
+            }
+            continue;
+        }
+        let is_collapsed = collapsed.contains(&key);
+        rows.push(Row::Group(schemas::GroupRow {
+            key,
+            label,
+            tasks: members.clone(),
+            collapsed: is_collapsed,
+            ..schemas::GroupRow::default()
+        }));
+        if !is_collapsed {
+            for i in members {
+                task_rows[i] = Some(rows.len());
+                rows.push(Row::Task(i));
+            }
         }
     }
+
+    (rows, task_rows)
 }
 
+/// Packs the tasks into the fewest lanes without overlaps: by start date, each task goes to the
+/// first lane free by then.
+fn lanes(tasks: &[usize], task_dates: &[Vec<(NaiveDateTime, NaiveDateTime)>]) -> Vec<Vec<usize>> {
+    let span = |i: usize| {
+        let dates = &task_dates[i];
+        let start = dates.iter().map(|it| it.0).min().unwrap_or(NaiveDateTime::MIN);
+        let end = dates.iter().map(|it| it.1).max().unwrap_or(start);
+        (start, end)
+    };
+    let mut order = tasks.to_vec();
+    order.sort_by_key(|i| span(*i).0);
+
+    let mut lanes: Vec<(NaiveDateTime, Vec<usize>)> = Vec::new();
+    for i in order {
+        let (start, end) = span(i);
+        match lanes.iter_mut().find(|(free, _)| *free <= start) {
+            Some((free, lane)) => {
+                *free = end;
+                lane.push(i);
+            }
+            None => lanes.push((end, vec![i])),
+        }
+    }
+
+    lanes.into_iter().map(|(_, lane)| lane).collect()
+}
+
+#[allow(clippy::too_many_arguments)]
 fn bar_task(
     task: schemas::Task,
     fmt: String,
-    dates_: &Vec<NaiveDateTime>,
+    dates_: &[NaiveDateTime],
     column_width: f64,
     props: &schemas::GanttProps,
+    theme: &Theme,
+    tz: &DisplayTimeZone,
     task_height: f64,
-    i: usize,
+    row: Option<usize>,
     row_height: f64,
 ) -> BarTask {
-    let start = NaiveDateTime::parse_from_str(&task.start.clone().unwrap(), &fmt).unwrap();
-    let end = NaiveDateTime::parse_from_str(&task.end.clone().unwrap(), &fmt).unwrap();
-    let x_1 = task_x_coordinate(start, dates_.to_owned(), column_width);
-    let x_2 = task_x_coordinate(end, dates_.to_owned(), column_width);
+    let rtl = props.display_option.rtl.unwrap_or(false);
+    let segments = task.segment_dates(&fmt, tz);
+    let start = segments.iter().map(|it| it.0).min().unwrap_or_else(time_zone::now);
+    let end = segments.iter().map(|it| it.1).max().unwrap_or(start);
+    // rtl timeline is mirrored, so the end of a task is on its left side
+    let span = |start: NaiveDateTime, end: NaiveDateTime| {
+        let x_start = task_x_coordinate(start, dates_, column_width);
+        let x_end = task_x_coordinate(end, dates_, column_width);
+        if rtl {
+            let svg_width = (dates_.len() as f64) * column_width;
+            (svg_width - x_end, svg_width - x_start)
+        } else {
+            (x_start, x_end)
+        }
+    };
+    let (x_1, x_2) = span(start, end);
+
+    // progress is a share of the work time, it fills the segments one after another
+    let work: i64 = segments.iter().map(|(start, end)| (*end - *start).num_milliseconds()).sum();
+    let mut done = (work as f64 * task.progress.unwrap_or(0.0) * 0.01) as i64;
+    let mut progress_end = if rtl { x_2 } else { x_1 };
+    let bar_segments: Vec<BarSegment> = segments
+        .iter()
+        .map(|(start, end)| {
+            let filled = done.clamp(0, (*end - *start).num_milliseconds());
+            done -= filled;
+            let (x_1, x_2) = span(*start, *end);
+            let (progress_1, progress_2) =
+                span(*start, *start + chrono::Duration::milliseconds(filled));
+            if filled > 0 {
+                progress_end = if rtl { progress_1 } else { progress_2 };
+            }
+            BarSegment {
+                x_1,
+                x_2,
+                progress_x: progress_1,
+                progress_width: progress_2 - progress_1,
+            }
+        })
+        .collect();
+    let (progress_x, progress_width) = if rtl {
+        (progress_end, x_2 - progress_end)
+    } else {
+        (x_1, progress_end - x_1)
+    };
+    let split = task.segments.as_ref().is_some_and(|it| !it.is_empty());
 
-    BarTask::default()
-        // .bar_children(childrens)
+    let mut bar = BarTask::default()
         .bar_corner_radius(props.style_option.bar_corner_radius.unwrap_or(3.0))
         .handle_width(props.style_option.handle_width.unwrap_or(8.0))
         .height(task_height)
-        .index(i as f64)
-        .progress_width((x_2 - x_1) * task.progress.unwrap() * 0.01)
-        .progress_x(x_1)
+        .progress_width(progress_width)
+        .progress_x(progress_x)
+        .segments(split.then_some(bar_segments))
         .type_internal(task.type_.clone().unwrap().get_internal())
         .x_1(x_1)
         .x_2(x_2)
-        .y((i as f64) * row_height + (row_height - task_height) / 2.0)
-        .task(task)
-        .styles(schemas::BarTaskStyles {
-            background_color: Some(
-                props
-                    .style_option
-                    .bar_background_color
-                    .clone()
-                    .unwrap_or_else(|| "#b8c2cc".to_owned()),
-            ),
-            background_selected_color: Some(
-                props
-                    .style_option
-                    .bar_background_selected_color
-                    .clone()
-                    .unwrap_or_else(|| "#aeb8c2".to_owned()),
-            ),
-            progress_color: Some(
-                props
-                    .style_option
-                    .bar_progress_color
-                    .clone()
-                    .unwrap_or_else(|| "#a3a3ff".to_owned()),
-            ),
-            progress_selected_color: Some(
-                props
-                    .style_option
-                    .bar_progress_selected_color
-                    .clone()
-                    .unwrap_or_else(|| "#8282f5".to_owned()),
-            ),
-        })
+        .styles(bar_task_styles(&task, theme))
+        .task(task);
+    // tasks without a row are not drawn and share no row with others
+    bar.index = row.map(|row| row as f64);
+    bar.y = row.map(|row| (row as f64) * row_height + (row_height - task_height) / 2.0);
+    bar
 }
 
-fn task_x_coordinate(x_date: NaiveDateTime, dates: Vec<NaiveDateTime>, column_width: f64) -> f64 {
-    let index = dates
-        .iter()
-        .position(|it| it.timestamp() >= x_date.timestamp())
-        .unwrap()
-        - 1;
+/// Colours by task type, as references to the theme variables.
+/// Colours set in `Task.styles` override them for this bar only.
+fn bar_task_styles(task: &schemas::Task, theme: &Theme) -> schemas::BarTaskStyles {
+    let (background, background_selected, progress, progress_selected) = match task.type_ {
+        Some(TaskType::Project) => (
+            var("--gantt-project-background-color", &theme.project_background_color),
+            var("--gantt-project-background-selected-color", &theme.project_background_selected_color),
+            var("--gantt-project-progress-color", &theme.project_progress_color),
+            var("--gantt-project-progress-selected-color", &theme.project_progress_selected_color),
+        ),
+        Some(TaskType::Milestone) => (
+            var("--gantt-milestone-background-color", &theme.milestone_background_color),
+            var("--gantt-milestone-background-selected-color", &theme.milestone_background_selected_color),
+            var("--gantt-bar-progress-color", &theme.bar_progress_color),
+            var("--gantt-bar-progress-selected-color", &theme.bar_progress_selected_color),
+        ),
+        _ => (
+            var("--gantt-bar-background-color", &theme.bar_background_color),
+            var("--gantt-bar-background-selected-color", &theme.bar_background_selected_color),
+            var("--gantt-bar-progress-color", &theme.bar_progress_color),
+            var("--gantt-bar-progress-selected-color", &theme.bar_progress_selected_color),
+        ),
+    };
+
+    let own = task.styles.clone().unwrap_or_default();
+    schemas::BarTaskStyles {
+        background_color: own.background_color.or(Some(background)),
+        background_selected_color: own.background_selected_color.or(Some(background_selected)),
+        progress_color: own.progress_color.or(Some(progress)),
+        progress_selected_color: own.progress_selected_color.or(Some(progress_selected)),
+    }
+}
 
-    let remainder = x_date.timestamp_nanos() - dates[index].timestamp_nanos();
-    let percent = remainder / (dates[index + 1].timestamp_nanos() - dates[index].timestamp_nanos());
+/// Places a marker on the timeline, ranges are clipped to the visible dates.
+fn grid_marker(
+    marker: &schemas::Marker,
+    fmt: &str,
+    tz: &DisplayTimeZone,
+    dates: &[NaiveDateTime],
+    column_width: f64,
+    theme: &Theme,
+    rtl: bool,
+) -> Option<schemas::GridMarker> {
+    let parse = |value: &String| match time_zone::parse_date(value, fmt, tz) {
+        Ok(date) => Some(date),
+        Err(err) => {
+            log::warn!("{}", err);
+            None
+        }
+    };
+    let start = parse(marker.date.as_ref()?)?;
+    let end = match &marker.end {
+        Some(end) => parse(end)?,
+        None => start,
+    };
+    let (first, last) = (*dates.first()?, *dates.last()?);
+    if end < first || start > last {
+        return None;
+    }
+
+    let x_start = task_x_coordinate(start, dates, column_width);
+    let x_end = task_x_coordinate(end, dates, column_width);
+    let svg_width = (dates.len() as f64) * column_width;
+    let (x_1, x_2) = if rtl {
+        (svg_width - x_end, svg_width - x_start)
+    } else {
+        (x_start, x_end)
+    };
 
-    (index as f64) * column_width + (percent as f64) * (column_width as f64)
+    Some(schemas::GridMarker {
+        x_1,
+        x_2,
+        is_range: marker.end.is_some(),
+        label: marker.label.clone().unwrap_or_default(),
+        color: marker
+            .color
+            .clone()
+            .unwrap_or_else(|| var("--gantt-marker-color", &theme.marker_color)),
+        dash: marker.dash.clone().unwrap_or_default(),
+    })
+}
+
+/// x of `x_date` on the timeline, the share of a column follows its actual length, so the 15th
+/// is near the middle of February as well as of March. Dates before the first column are placed
+/// at 0, dates after the last boundary at that boundary.
+fn task_x_coordinate(x_date: NaiveDateTime, dates: &[NaiveDateTime], column_width: f64) -> f64 {
+    let (Some(first), Some(last)) = (dates.first(), dates.last()) else {
+        return 0.0;
+    };
+    if x_date <= *first {
+        return 0.0;
+    }
+    if x_date >= *last {
+        return ((dates.len() - 1) as f64) * column_width;
+    }
+
+    // first boundary after the date, there is one before it too
+    let index = dates.iter().position(|it| *it > x_date).unwrap() - 1;
+    let column = (dates[index + 1] - dates[index]).num_milliseconds() as f64;
+    let offset = (x_date - dates[index]).num_milliseconds() as f64;
+
+    (index as f64 + offset / column) * column_width
+}
+
+/// `duration` rounded to whole `step` milliseconds, no rounding for steps below one.
+fn time_step(duration: chrono::Duration, step: f64) -> chrono::Duration {
+    if step < 1.0 {
+        return duration;
+    }
+    let steps = (duration.num_milliseconds() as f64 / step).round();
+    chrono::Duration::milliseconds((steps * step) as i64)
 }
 
-fn seed_dates(start: NaiveDateTime, end: NaiveDateTime, mode: ViewMode) -> Vec<NaiveDateTime> {
-    let mut current = mode.get_mod_date(start, false);
+/// Date at `x` on the timeline, the inverse of `task_x_coordinate`. `x` before the first
+/// column gives the first boundary, `x` past the last column the last boundary.
+fn x_date(x: f64, dates: &[NaiveDateTime], column_width: f64) -> NaiveDateTime {
+    let (Some(first), Some(last)) = (dates.first(), dates.last()) else {
+        return time_zone::now();
+    };
+    let position = (x / column_width).max(0.0);
+    let index = position.floor() as usize;
+    if index + 1 >= dates.len() {
+        return *last;
+    }
+    let column = (dates[index + 1] - dates[index]).num_milliseconds() as f64;
+    let offset = chrono::Duration::milliseconds((column * position.fract()) as i64);
+
+    (dates[index] + offset).max(*first)
+}
+
+/// View mode and column width for `column_width` wide columns of `scale`. Columns narrower
+/// than `min_width` or wider than `max_width` switch to the next coarser or finer view mode
+/// of `ZOOM_LEVELS` at the same time density, around `date`. Other view modes keep their mode.
+fn zoom_level(
+    scale: &Scale,
+    column_width: f64,
+    min_width: f64,
+    max_width: f64,
+    date: NaiveDateTime,
+) -> (ViewMode, f64) {
+    let mut scale = scale.clone();
+    let mut column_width = column_width;
+    for _ in 0..ZOOM_LEVELS.len() {
+        let level = ZOOM_LEVELS.iter().position(|it| *it == scale.mode);
+        let next = if column_width < min_width {
+            level.and_then(|i| ZOOM_LEVELS.get(i + 1))
+        } else if column_width > max_width {
+            level.and_then(|i| i.checked_sub(1)).map(|i| &ZOOM_LEVELS[i])
+        } else {
+            break;
+        };
+        let Some(mode) = next else {
+            break;
+        };
+        let next = Scale::new(mode.clone(), scale.options.clone());
+        column_width *= next.column_minutes(date) / scale.column_minutes(date);
+        scale = next;
+    }
+
+    (scale.mode, column_width.clamp(min_width, max_width))
+}
+
+/// Column boundaries from the period containing `start` to the one containing `end`,
+/// padded with `pre_steps` columns on both sides.
+fn seed_dates(
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    scale: &Scale,
+    pre_steps: usize,
+    tz: &DisplayTimeZone,
+) -> Vec<NaiveDateTime> {
+    let mut current = time_zone::snap(scale, start, tz);
+    for _ in 0..pre_steps {
+        current = time_zone::step(scale, current, false, tz);
+    }
+    let mut last = time_zone::snap(scale, end, tz);
+    if last < end {
+        last = time_zone::step(scale, last, true, tz);
+    }
+    for _ in 0..pre_steps {
+        last = time_zone::step(scale, last, true, tz);
+    }
     let mut dates: Vec<NaiveDateTime> = Vec::new();
     dates.push(current);
-    while current < mode.get_mod_date(end, true) {
-        current = mode.get_mod_date(current, true);
+    while current < last {
+        current = time_zone::step(scale, current, true, tz);
         dates.push(current);
     }
 
     dates
 }
 
+/// Time scale of `view_mode` with the settings of the configurable modes. Sprints start in the
+/// week of the first task unless `sprintAnchor` is set.
+fn scale(
+    display_option: &schemas::DisplayOption,
+    view_mode: &ViewMode,
+    week_start: chrono::Weekday,
+    first_task: NaiveDateTime,
+    fmt: &str,
+    tz: &DisplayTimeZone,
+) -> Scale {
+    let defaults = ScaleOptions::default();
+    let week = Scale::new(ViewMode::Week, ScaleOptions { week_start, ..defaults.clone() });
+    let sprint_anchor = display_option
+        .sprint_anchor
+        .as_ref()
+        .and_then(|value| {
+            time_zone::parse_date(value, fmt, tz)
+                .map_err(|err| log::warn!("{}", err))
+                .ok()
+        })
+        .map(|date| tz.to_local(date))
+        .unwrap_or_else(|| week.start_of(tz.to_local(first_task)));
+
+    Scale::new(
+        view_mode.clone(),
+        ScaleOptions {
+            week_start,
+            minute_step: display_option.minute_step.unwrap_or(defaults.minute_step),
+            sprint_weeks: display_option.sprint_weeks.unwrap_or(defaults.sprint_weeks),
+            sprint_anchor,
+            fiscal_year_start_month: display_option
+                .fiscal_year_start_month
+                .unwrap_or(defaults.fiscal_year_start_month)
+                .clamp(1, 12),
+        },
+    )
+}
+
+/// Parse format of the task dates. `locale` used to hold it, such values are still honoured.
+fn date_format(display_option: &schemas::DisplayOption) -> String {
+    if let Some(fmt) = &display_option.date_format {
+        return fmt.clone();
+    }
+    match &display_option.locale {
+        Some(locale) if locale.contains('%') => {
+            log::warn!("`locale` is a language code, use `dateFormat` for {}", locale);
+            locale.clone()
+        }
+        _ => "%Y-%m-%d %H:%M:%S".to_owned(),
+    }
+}
+
+/// Accepts or rejects an edit of the chart or the table. Work periods have to end after they
+/// start, then `validate` has the last word.
+pub(crate) fn check_task_change(
+    validate: &Option<yew::Callback<schemas::TaskChange, Result<(), String>>>,
+    original: &schemas::Task,
+    changed: &schemas::Task,
+    fmt: &str,
+    tz: &DisplayTimeZone,
+) -> Result<(), String> {
+    let segments = changed.segment_dates(fmt, tz);
+    if segments.is_empty() || segments.iter().any(|(start, end)| start > end) {
+        return Err("The start has to be before the end".to_owned());
+    }
+    match validate {
+        Some(validate) => validate.emit(schemas::TaskChange {
+            original: original.clone(),
+            changed: changed.clone(),
+        }),
+        None => Ok(()),
+    }
+}
+
+fn display_time_zone(display_option: &schemas::DisplayOption) -> DisplayTimeZone {
+    match &display_option.time_zone {
+        Some(value) => DisplayTimeZone::parse(value).unwrap_or_else(|| {
+            log::warn!("unknown time zone {}", value);
+            DisplayTimeZone::default()
+        }),
+        None => DisplayTimeZone::default(),
+    }
+}
+
 
 #[derive(Debug, yew::Properties, PartialEq)]
 pub struct Props {
@@ -359,30 +1739,416 @@ pub struct Props {
     pub style_option: String
 }
 
-#[yew::function_component(App)]
-fn app(props: &Props) -> Html {
-    let tasks_: Vec<schemas::Task> = serde_json::from_str(&props.task).unwrap();
-    html! {
-        <Gantt
-            event_option = {schemas::EventOption::default()}
-            display_option = {schemas::DisplayOption::default()}
-            style_option = {schemas::StylingOption::default()}
-            tasks = {tasks_}
-        />
+impl From<&Props> for schemas::GanttProps {
+    fn from(props: &Props) -> Self {
+        schemas::GanttProps {
+            tasks: Some(serde_json::from_str(&props.task).unwrap()),
+            event_option: options("event", &props.event_option),
+            display_option: options("display", &props.display_option),
+            style_option: options("style", &props.style_option),
+        }
     }
 }
 
+/// Options given as JSON, empty strings fall back to the defaults. So do invalid ones, with a
+/// warning.
+fn options<T: serde::de::DeserializeOwned + Default>(name: &str, json: &str) -> T {
+    if json.trim().is_empty() {
+        return T::default();
+    }
+    serde_json::from_str(json).unwrap_or_else(|err| {
+        log::warn!("invalid {} options, using the defaults: {}", name, err);
+        T::default()
+    })
+}
+
+/// Chart rendered by [`render`].
+#[wasm_bindgen::prelude::wasm_bindgen]
+pub struct GanttHandle {
+    handle: yew::AppHandle<Gantt>,
+    display_option: schemas::DisplayOption,
+}
+
 #[wasm_bindgen::prelude::wasm_bindgen]
-pub fn render(selector: &str, task: &str, event_option: &str, display_option: &str, style_option: &str) {
+impl GanttHandle {
+    /// Zooms so the range fills the visible width, the dates are read like task dates.
+    #[wasm_bindgen(js_name = zoomToRange)]
+    pub fn zoom_to_range(&self, start: &str, end: &str) {
+        let fmt = date_format(&self.display_option);
+        let tz = display_time_zone(&self.display_option);
+        match (
+            time_zone::parse_date(start, &fmt, &tz),
+            time_zone::parse_date(end, &fmt, &tz),
+        ) {
+            (Ok(start), Ok(end)) => self.handle.send_message(GanttMsg::ZoomToRange(start, end)),
+            (Err(err), _) | (_, Err(err)) => log::warn!("{}", err),
+        }
+    }
+
+    /// Splits the task at the date, read like task dates. The task has to be running then.
+    #[wasm_bindgen(js_name = splitTask)]
+    pub fn split_task(&self, id: &str, date: &str) {
+        let fmt = date_format(&self.display_option);
+        let tz = display_time_zone(&self.display_option);
+        match time_zone::parse_date(date, &fmt, &tz) {
+            Ok(date) => self.handle.send_message(GanttMsg::SplitTask(id.to_owned(), date)),
+            Err(err) => log::warn!("{}", err),
+        }
+    }
+
+    /// Shows only the tasks meeting the filter, given like the `filter` display option or as a
+    /// function getting the task. `null` goes back to the display option.
+    pub fn filter(&self, filter: wasm_bindgen::JsValue) {
+        let filter = if filter.is_null() || filter.is_undefined() {
+            None
+        } else if let Some(predicate) = filter.dyn_ref::<js_sys::Function>() {
+            let predicate = predicate.clone();
+            Some(schemas::TaskFilter::default().predicate(yew::Callback::from(
+                move |task: schemas::Task| {
+                    let task = serde_wasm_bindgen::to_value(&task).unwrap_or_default();
+                    let shown = predicate.call1(&wasm_bindgen::JsValue::NULL, &task);
+                    shown.map_or(true, |it| it.is_truthy())
+                },
+            )))
+        } else {
+            match serde_wasm_bindgen::from_value(filter) {
+                Ok(filter) => Some(filter),
+                Err(err) => {
+                    log::warn!("{}", err);
+                    return;
+                }
+            }
+        };
+        self.handle.send_message(GanttMsg::Filter(filter));
+    }
+
+    /// Makes the task with the id, or the selected one, part of the task above it in
+    /// `displayOrder`, which becomes a project if it is not one yet.
+    pub fn indent(&self, id: Option<String>) {
+        self.handle.send_message(GanttMsg::Indent(id));
+    }
+
+    /// Moves the task with the id, or the selected one, out of its project to right after it.
+    pub fn outdent(&self, id: Option<String>) {
+        self.handle.send_message(GanttMsg::Outdent(id));
+    }
+
+    /// Fills in the quick search.
+    pub fn search(&self, query: &str) {
+        self.handle.send_message(GanttMsg::Search(query.to_owned()));
+    }
+}
+
+#[wasm_bindgen::prelude::wasm_bindgen]
+pub fn render(
+    selector: &str,
+    task: &str,
+    event_option: &str,
+    display_option: &str,
+    style_option: &str,
+) -> GanttHandle {
     let props = Props {
         task: task.to_string(), 
         event_option: event_option.to_string(), 
         display_option: display_option.to_string(), 
         style_option: style_option.to_string()
     };
+    let props = schemas::GanttProps::from(&props);
     let window = web_sys::window().expect("no global `window` exists");
     let document = window.document().expect("should have a document on window");
     let element = document.query_selector(selector).expect("no element exists").unwrap();
 
-    yew::Renderer::<App>::with_root_and_props(element, props).render();
-}
\ No newline at end of file
+    GanttHandle {
+        display_option: props.display_option.clone(),
+        handle: yew::Renderer::<Gantt>::with_root_and_props(element, props).render(),
+    }
+}
+
+/// Tasks of a Microsoft Project XML file, as JSON for [`render`]. The dates are written like
+/// the display option reads them.
+#[wasm_bindgen::prelude::wasm_bindgen(js_name = importMspdi)]
+pub fn import_mspdi(xml: &str, display_option: &str) -> Result<String, wasm_bindgen::JsValue> {
+    let display_option = options("display", display_option);
+    let fmt = date_format(&display_option);
+    let tz = display_time_zone(&display_option);
+    mspdi::import(xml, &fmt, &tz)
+        .and_then(|tasks| serde_json::to_string(&tasks).map_err(|err| err.to_string()))
+        .map_err(|err| wasm_bindgen::JsValue::from_str(&err))
+}
+
+/// Microsoft Project XML file of the tasks, given as JSON like for [`render`].
+#[wasm_bindgen::prelude::wasm_bindgen(js_name = exportMspdi)]
+pub fn export_mspdi(tasks: &str, display_option: &str) -> Result<String, wasm_bindgen::JsValue> {
+    let tasks: Vec<schemas::Task> = serde_json::from_str(tasks)
+        .map_err(|err| wasm_bindgen::JsValue::from_str(&err.to_string()))?;
+    let display_option = options("display", display_option);
+    let fmt = date_format(&display_option);
+    let tz = display_time_zone(&display_option);
+    Ok(mspdi::export(&tasks, &fmt, &tz))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap().and_hms_opt(0, 0, 0).unwrap()
+    }
+
+    fn assert_near(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} is not {}", actual, expected);
+    }
+
+    #[test]
+    fn positions_follow_month_lengths() {
+        let dates = [date(2023, 1, 1), date(2023, 2, 1), date(2023, 3, 1), date(2023, 4, 1)];
+        // the 28th is near the end of February but not of January
+        assert_near(task_x_coordinate(date(2023, 1, 28), &dates, 100.0), 100.0 * 27.0 / 31.0);
+        assert_near(
+            task_x_coordinate(date(2023, 2, 28), &dates, 100.0),
+            100.0 + 100.0 * 27.0 / 28.0,
+        );
+        assert_near(task_x_coordinate(date(2023, 3, 1), &dates, 100.0), 200.0);
+    }
+
+    #[test]
+    fn positions_follow_leap_years() {
+        let dates = [date(2023, 1, 1), date(2024, 1, 1), date(2025, 1, 1)];
+        assert_near(task_x_coordinate(date(2023, 7, 1), &dates, 100.0), 100.0 * 181.0 / 365.0);
+        assert_near(
+            task_x_coordinate(date(2024, 7, 1), &dates, 100.0),
+            100.0 + 100.0 * 182.0 / 366.0,
+        );
+    }
+
+    #[test]
+    fn positions_outside_the_columns_are_clamped() {
+        let dates = [date(2023, 1, 1), date(2023, 2, 1), date(2023, 3, 1)];
+        // the first boundary itself used to underflow the column index
+        assert_near(task_x_coordinate(date(2023, 1, 1), &dates, 100.0), 0.0);
+        assert_near(task_x_coordinate(date(2022, 12, 1), &dates, 100.0), 0.0);
+        assert_near(task_x_coordinate(date(2023, 3, 1), &dates, 100.0), 200.0);
+        assert_near(task_x_coordinate(date(2023, 6, 1), &dates, 100.0), 200.0);
+        assert_near(task_x_coordinate(date(2023, 1, 1), &[], 100.0), 0.0);
+        assert_eq!(x_date(-50.0, &dates, 100.0), date(2023, 1, 1));
+        assert_eq!(x_date(250.0, &dates, 100.0), date(2023, 3, 1));
+    }
+
+    #[test]
+    fn dates_invert_positions() {
+        let dates = [date(2024, 1, 1), date(2024, 2, 1), date(2024, 3, 1)];
+        for day in [date(2024, 1, 1), date(2024, 1, 17), date(2024, 2, 29)] {
+            assert_eq!(x_date(task_x_coordinate(day, &dates, 100.0), &dates, 100.0), day);
+        }
+    }
+
+    #[test]
+    fn tasks_apart_share_a_lane() {
+        let dates = [
+            vec![(date(2024, 1, 1), date(2024, 1, 5))],
+            vec![(date(2024, 1, 10), date(2024, 1, 12))],
+            vec![(date(2024, 2, 1), date(2024, 2, 3))],
+        ];
+        assert_eq!(lanes(&[2, 0, 1], &dates), vec![vec![0, 1, 2]]);
+    }
+
+    #[test]
+    fn overlapping_tasks_split_lanes() {
+        let dates = [
+            vec![(date(2024, 1, 1), date(2024, 1, 10))],
+            vec![(date(2024, 1, 5), date(2024, 1, 15))],
+            vec![(date(2024, 1, 8), date(2024, 1, 9))],
+            vec![(date(2024, 1, 12), date(2024, 1, 20))],
+        ];
+        // the fourth task reuses the first lane that is free again
+        assert_eq!(lanes(&[0, 1, 2, 3], &dates), vec![vec![0, 3], vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn touching_tasks_share_a_lane() {
+        let dates = [
+            vec![(date(2024, 1, 1), date(2024, 1, 5))],
+            vec![(date(2024, 1, 5), date(2024, 1, 8))],
+            vec![(date(2024, 1, 7), date(2024, 1, 9))],
+        ];
+        assert_eq!(lanes(&[0, 1, 2], &dates), vec![vec![0, 1], vec![2]]);
+    }
+
+    #[test]
+    fn split_tasks_take_their_whole_span() {
+        let dates = [
+            vec![(date(2024, 1, 1), date(2024, 1, 3)), (date(2024, 1, 10), date(2024, 1, 12))],
+            vec![(date(2024, 1, 5), date(2024, 1, 7))],
+        ];
+        // the second task fits in the gap but the lane is taken until the last segment ends
+        assert_eq!(lanes(&[0, 1], &dates), vec![vec![0], vec![1]]);
+    }
+
+    fn task(id: &str, dependencies: &[&str]) -> schemas::Task {
+        schemas::Task {
+            id: Some(id.to_string()),
+            dependencies: Some(dependencies.iter().map(|it| it.to_string()).collect()),
+            ..schemas::Task::default()
+        }
+    }
+
+    fn span(start: u32, end: u32) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+        vec![(date(2024, 1, start), date(2024, 1, end))]
+    }
+
+    fn sorted_set(set: HashSet<usize>) -> Vec<usize> {
+        let mut items: Vec<usize> = set.into_iter().collect();
+        items.sort();
+        items
+    }
+
+    #[test]
+    fn chains_without_float_are_critical() {
+        let tasks = [task("a", &[]), task("b", &["a"]), task("c", &[])];
+        let dates = [span(1, 5), span(5, 10), span(1, 3)];
+        assert_eq!(sorted_set(critical(&tasks, &dates)), vec![0, 1]);
+    }
+
+    #[test]
+    fn parallel_chains_are_all_critical() {
+        let tasks = [task("a", &[]), task("b", &["a"]), task("c", &[]), task("d", &["c"])];
+        let dates = [span(1, 5), span(5, 10), span(1, 8), span(8, 10)];
+        assert_eq!(sorted_set(critical(&tasks, &dates)), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn dependencies_ending_early_have_float() {
+        let tasks = [task("a", &[]), task("b", &["a"]), task("c", &["a"])];
+        let dates = [span(1, 3), span(5, 10), span(3, 6)];
+        assert_eq!(sorted_set(critical(&tasks, &dates)), vec![1]);
+    }
+
+    #[test]
+    fn overlapping_dependencies_push_their_successors() {
+        // b cannot start before a ends, so it ends a day after c
+        let tasks = [task("a", &[]), task("b", &["a"]), task("c", &[])];
+        let dates = [span(1, 6), span(5, 10), span(1, 10)];
+        assert_eq!(sorted_set(critical(&tasks, &dates)), vec![0, 1]);
+    }
+
+    #[test]
+    fn dependency_cycles_are_cut() {
+        let tasks = [task("a", &["b"]), task("b", &["a"]), task("c", &["missing"])];
+        let dates = [span(1, 5), span(5, 10), vec![]];
+        assert_eq!(sorted_set(critical(&tasks, &dates)), vec![0, 1]);
+    }
+
+    #[test]
+    fn filters_late_tasks() {
+        let past = vec![(date(2000, 1, 1), date(2000, 1, 2))];
+        let future = vec![(date(2000, 1, 1), date(2999, 1, 1))];
+        let done = schemas::Task { progress: Some(100.0), ..task("done", &[]) };
+        let tasks = [task("late", &[]), done, task("running", &[]), task("undated", &[])];
+        let dates = [past.clone(), past, future, vec![]];
+        let late = schemas::TaskFilter::default().late(true);
+        assert_eq!(filtered(&late, &tasks, &dates), vec![true, false, false, false]);
+        let on_time = schemas::TaskFilter::default().late(false);
+        assert_eq!(filtered(&on_time, &tasks, &dates), vec![false, true, true, true]);
+    }
+
+    #[test]
+    fn filters_combine() {
+        let mut tasks = [task("a", &[]), task("b", &["a"]), task("c", &[])];
+        tasks[1].assignee = Some("ann".to_string());
+        tasks[2].assignee = Some("ann".to_string());
+        let dates = [span(1, 5), span(5, 10), span(1, 3)];
+        let filter = schemas::TaskFilter::default().critical(true).assigned_to("ann".to_string());
+        assert_eq!(filtered(&filter, &tasks, &dates), vec![false, true, false]);
+    }
+
+    fn outline_task(id: &str, project: Option<&str>, order: f64) -> schemas::Task {
+        schemas::Task {
+            id: Some(id.to_string()),
+            project: project.map(str::to_string),
+            display_order: Some(order),
+            type_: Some(TaskType::Task),
+            start: Some("2024-01-01 00:00:00".to_string()),
+            end: Some("2024-01-02 00:00:00".to_string()),
+            ..schemas::Task::default()
+        }
+    }
+
+    fn project(id: &str, parent: Option<&str>, order: f64) -> schemas::Task {
+        schemas::Task { type_: Some(TaskType::Project), ..outline_task(id, parent, order) }
+    }
+
+    fn ids(tasks: &[schemas::Task]) -> Vec<&str> {
+        let tz = DisplayTimeZone::default();
+        let dates: Vec<_> = tasks.iter().map(|_| Vec::new()).collect();
+        let order = sorted(tasks, &dates, None, "%Y-%m-%d %H:%M:%S", &tz);
+        order.iter().map(|i| tasks[*i].id.as_deref().unwrap()).collect()
+    }
+
+    fn gantt(tasks: Vec<schemas::Task>) -> Gantt {
+        Gantt { tasks, column_width: 60.0, ..Gantt::default() }
+    }
+
+    #[test]
+    fn outline_numbers_nest_under_projects() {
+        let tasks = [
+            project("p", None, 1.0),
+            outline_task("a", Some("p"), 2.0),
+            project("q", Some("p"), 3.0),
+            outline_task("b", Some("q"), 4.0),
+            outline_task("c", None, 5.0),
+            outline_task("d", Some("missing"), 6.0),
+        ];
+        let (wbs, levels) = outline(&tasks, &[0, 1, 2, 3, 4, 5]);
+        assert_eq!(wbs, ["1", "1.1", "1.2", "1.2.1", "2", "3"]);
+        assert_eq!(levels, [0, 1, 1, 2, 0, 0]);
+        // numbers follow the order given, children after their project
+        let (wbs, _) = outline(&tasks, &[4, 0, 2, 3, 1, 5]);
+        assert_eq!(wbs, ["2", "2.2", "2.1", "2.1.1", "1", "3"]);
+    }
+
+    #[test]
+    fn indent_makes_the_previous_sibling_a_project() {
+        let tasks = vec![outline_task("a", None, 1.0), outline_task("b", None, 2.0)];
+        let mut gantt = gantt(tasks);
+        let props = schemas::GanttProps::default();
+        assert!(gantt.indent(&props, Some("b".to_string()), true));
+        assert_eq!(gantt.tasks[1].project.as_deref(), Some("a"));
+        assert_eq!(gantt.tasks[0].type_, Some(TaskType::Project));
+        // the first task has nothing to go into
+        assert!(!gantt.indent(&props, Some("a".to_string()), true));
+    }
+
+    #[test]
+    fn indent_skips_tasks_of_other_projects() {
+        let tasks = vec![
+            project("p", None, 1.0),
+            outline_task("x", Some("p"), 2.0),
+            outline_task("b", None, 3.0),
+        ];
+        let mut gantt = gantt(tasks);
+        let props = schemas::GanttProps::default();
+        assert!(gantt.indent(&props, Some("b".to_string()), true));
+        assert_eq!(gantt.tasks[2].project.as_deref(), Some("p"));
+        assert_eq!(gantt.tasks[1].type_, Some(TaskType::Task));
+        assert_eq!(ids(&gantt.tasks), ["p", "x", "b"]);
+    }
+
+    #[test]
+    fn outdent_moves_after_the_project() {
+        let tasks = vec![
+            project("p", None, 1.0),
+            outline_task("x", Some("p"), 2.0),
+            outline_task("y", Some("p"), 3.0),
+            outline_task("z", None, 4.0),
+        ];
+        let mut gantt = gantt(tasks);
+        let props = schemas::GanttProps::default();
+        assert!(gantt.indent(&props, Some("x".to_string()), false));
+        assert_eq!(gantt.tasks[1].project, None);
+        assert_eq!(ids(&gantt.tasks), ["p", "y", "x", "z"]);
+        // top level tasks stay where they are
+        assert!(!gantt.indent(&props, Some("z".to_string()), false));
+    }
+}
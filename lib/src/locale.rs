@@ -0,0 +1,249 @@
+use std::fmt::Write;
+
+use chrono::{Datelike, NaiveDateTime, Weekday};
+
+/// Month and weekday names of a language, weekdays start on Monday.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Locale {
+    /// ISO 639-1 and ISO 639-2 codes of the language
+    pub codes: &'static [&'static str],
+    pub months: [&'static str; 12],
+    pub months_short: [&'static str; 12],
+    pub weekdays: [&'static str; 7],
+    pub weekdays_short: [&'static str; 7],
+    /// Day with month, e.g. `Apr 20` or `20. Apr`
+    pub day_month: &'static str,
+    /// Month with year, e.g. `April 2023` or `2023年4月`
+    pub month_year: &'static str,
+    /// First day of the week in the calendar
+    pub week_start: Weekday,
+}
+
+static LOCALES: [Locale; 16] = [
+    Locale {
+        codes: &["en", "eng"],
+        months: ["January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December"],
+        months_short: ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"],
+        weekdays: ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"],
+        weekdays_short: ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"],
+        day_month: "%b %-d",
+        month_year: "%B %Y",
+        week_start: Weekday::Sun,
+    },
+    Locale {
+        codes: &["de", "deu", "ger"],
+        months: ["Januar", "Februar", "März", "April", "Mai", "Juni", "Juli", "August", "September", "Oktober", "November", "Dezember"],
+        months_short: ["Jan", "Feb", "Mär", "Apr", "Mai", "Jun", "Jul", "Aug", "Sep", "Okt", "Nov", "Dez"],
+        weekdays: ["Montag", "Dienstag", "Mittwoch", "Donnerstag", "Freitag", "Samstag", "Sonntag"],
+        weekdays_short: ["Mo", "Di", "Mi", "Do", "Fr", "Sa", "So"],
+        day_month: "%-d. %b",
+        month_year: "%B %Y",
+        week_start: Weekday::Mon,
+    },
+    Locale {
+        codes: &["fr", "fra", "fre"],
+        months: ["janvier", "février", "mars", "avril", "mai", "juin", "juillet", "août", "septembre", "octobre", "novembre", "décembre"],
+        months_short: ["janv.", "févr.", "mars", "avr.", "mai", "juin", "juil.", "août", "sept.", "oct.", "nov.", "déc."],
+        weekdays: ["lundi", "mardi", "mercredi", "jeudi", "vendredi", "samedi", "dimanche"],
+        weekdays_short: ["lun.", "mar.", "mer.", "jeu.", "ven.", "sam.", "dim."],
+        day_month: "%-d %b",
+        month_year: "%B %Y",
+        week_start: Weekday::Mon,
+    },
+    Locale {
+        codes: &["es", "spa"],
+        months: ["enero", "febrero", "marzo", "abril", "mayo", "junio", "julio", "agosto", "septiembre", "octubre", "noviembre", "diciembre"],
+        months_short: ["ene", "feb", "mar", "abr", "may", "jun", "jul", "ago", "sept", "oct", "nov", "dic"],
+        weekdays: ["lunes", "martes", "miércoles", "jueves", "viernes", "sábado", "domingo"],
+        weekdays_short: ["lun", "mar", "mié", "jue", "vie", "sáb", "dom"],
+        day_month: "%-d %b",
+        month_year: "%B %Y",
+        week_start: Weekday::Mon,
+    },
+    Locale {
+        codes: &["it", "ita"],
+        months: ["gennaio", "febbraio", "marzo", "aprile", "maggio", "giugno", "luglio", "agosto", "settembre", "ottobre", "novembre", "dicembre"],
+        months_short: ["gen", "feb", "mar", "apr", "mag", "giu", "lug", "ago", "set", "ott", "nov", "dic"],
+        weekdays: ["lunedì", "martedì", "mercoledì", "giovedì", "venerdì", "sabato", "domenica"],
+        weekdays_short: ["lun", "mar", "mer", "gio", "ven", "sab", "dom"],
+        day_month: "%-d %b",
+        month_year: "%B %Y",
+        week_start: Weekday::Mon,
+    },
+    Locale {
+        codes: &["pt", "por"],
+        months: ["janeiro", "fevereiro", "março", "abril", "maio", "junho", "julho", "agosto", "setembro", "outubro", "novembro", "dezembro"],
+        months_short: ["jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez"],
+        weekdays: ["segunda-feira", "terça-feira", "quarta-feira", "quinta-feira", "sexta-feira", "sábado", "domingo"],
+        weekdays_short: ["seg", "ter", "qua", "qui", "sex", "sáb", "dom"],
+        day_month: "%-d %b",
+        month_year: "%B %Y",
+        week_start: Weekday::Sun,
+    },
+    Locale {
+        codes: &["nl", "nld", "dut"],
+        months: ["januari", "februari", "maart", "april", "mei", "juni", "juli", "augustus", "september", "oktober", "november", "december"],
+        months_short: ["jan", "feb", "mrt", "apr", "mei", "jun", "jul", "aug", "sep", "okt", "nov", "dec"],
+        weekdays: ["maandag", "dinsdag", "woensdag", "donderdag", "vrijdag", "zaterdag", "zondag"],
+        weekdays_short: ["ma", "di", "wo", "do", "vr", "za", "zo"],
+        day_month: "%-d %b",
+        month_year: "%B %Y",
+        week_start: Weekday::Mon,
+    },
+    Locale {
+        codes: &["pl", "pol"],
+        months: ["styczeń", "luty", "marzec", "kwiecień", "maj", "czerwiec", "lipiec", "sierpień", "wrzesień", "październik", "listopad", "grudzień"],
+        months_short: ["sty", "lut", "mar", "kwi", "maj", "cze", "lip", "sie", "wrz", "paź", "lis", "gru"],
+        weekdays: ["poniedziałek", "wtorek", "środa", "czwartek", "piątek", "sobota", "niedziela"],
+        weekdays_short: ["pon", "wt", "śr", "czw", "pt", "sob", "nd"],
+        day_month: "%-d %b",
+        month_year: "%B %Y",
+        week_start: Weekday::Mon,
+    },
+    Locale {
+        codes: &["ru", "rus"],
+        months: ["январь", "февраль", "март", "апрель", "май", "июнь", "июль", "август", "сентябрь", "октябрь", "ноябрь", "декабрь"],
+        months_short: ["янв", "фев", "мар", "апр", "май", "июн", "июл", "авг", "сен", "окт", "ноя", "дек"],
+        weekdays: ["понедельник", "вторник", "среда", "четверг", "пятница", "суббота", "воскресенье"],
+        weekdays_short: ["пн", "вт", "ср", "чт", "пт", "сб", "вс"],
+        day_month: "%-d %b",
+        month_year: "%B %Y",
+        week_start: Weekday::Mon,
+    },
+    Locale {
+        codes: &["uk", "ukr"],
+        months: ["січень", "лютий", "березень", "квітень", "травень", "червень", "липень", "серпень", "вересень", "жовтень", "листопад", "грудень"],
+        months_short: ["січ", "лют", "бер", "кві", "тра", "чер", "лип", "сер", "вер", "жов", "лис", "гру"],
+        weekdays: ["понеділок", "вівторок", "середа", "четвер", "пʼятниця", "субота", "неділя"],
+        weekdays_short: ["пн", "вт", "ср", "чт", "пт", "сб", "нд"],
+        day_month: "%-d %b",
+        month_year: "%B %Y",
+        week_start: Weekday::Mon,
+    },
+    Locale {
+        codes: &["sv", "swe"],
+        months: ["januari", "februari", "mars", "april", "maj", "juni", "juli", "augusti", "september", "oktober", "november", "december"],
+        months_short: ["jan", "feb", "mar", "apr", "maj", "jun", "jul", "aug", "sep", "okt", "nov", "dec"],
+        weekdays: ["måndag", "tisdag", "onsdag", "torsdag", "fredag", "lördag", "söndag"],
+        weekdays_short: ["mån", "tis", "ons", "tors", "fre", "lör", "sön"],
+        day_month: "%-d %b",
+        month_year: "%B %Y",
+        week_start: Weekday::Mon,
+    },
+    Locale {
+        codes: &["tr", "tur"],
+        months: ["Ocak", "Şubat", "Mart", "Nisan", "Mayıs", "Haziran", "Temmuz", "Ağustos", "Eylül", "Ekim", "Kasım", "Aralık"],
+        months_short: ["Oca", "Şub", "Mar", "Nis", "May", "Haz", "Tem", "Ağu", "Eyl", "Eki", "Kas", "Ara"],
+        weekdays: ["Pazartesi", "Salı", "Çarşamba", "Perşembe", "Cuma", "Cumartesi", "Pazar"],
+        weekdays_short: ["Pzt", "Sal", "Çar", "Per", "Cum", "Cmt", "Paz"],
+        day_month: "%-d %b",
+        month_year: "%B %Y",
+        week_start: Weekday::Mon,
+    },
+    Locale {
+        codes: &["ja", "jpn"],
+        months: ["1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月"],
+        months_short: ["1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月"],
+        weekdays: ["月曜日", "火曜日", "水曜日", "木曜日", "金曜日", "土曜日", "日曜日"],
+        weekdays_short: ["月", "火", "水", "木", "金", "土", "日"],
+        day_month: "%-m月%-d日",
+        month_year: "%Y年%-m月",
+        week_start: Weekday::Sun,
+    },
+    Locale {
+        codes: &["zh", "zho", "chi"],
+        months: ["一月", "二月", "三月", "四月", "五月", "六月", "七月", "八月", "九月", "十月", "十一月", "十二月"],
+        months_short: ["1月", "2月", "3月", "4月", "5月", "6月", "7月", "8月", "9月", "10月", "11月", "12月"],
+        weekdays: ["星期一", "星期二", "星期三", "星期四", "星期五", "星期六", "星期日"],
+        weekdays_short: ["周一", "周二", "周三", "周四", "周五", "周六", "周日"],
+        day_month: "%-m月%-d日",
+        month_year: "%Y年%-m月",
+        week_start: Weekday::Mon,
+    },
+    Locale {
+        codes: &["he", "heb", "iw"],
+        months: ["ינואר", "פברואר", "מרץ", "אפריל", "מאי", "יוני", "יולי", "אוגוסט", "ספטמבר", "אוקטובר", "נובמבר", "דצמבר"],
+        months_short: ["ינו׳", "פבר׳", "מרץ", "אפר׳", "מאי", "יוני", "יולי", "אוג׳", "ספט׳", "אוק׳", "נוב׳", "דצמ׳"],
+        weekdays: ["יום שני", "יום שלישי", "יום רביעי", "יום חמישי", "יום שישי", "יום שבת", "יום ראשון"],
+        weekdays_short: ["יום ב׳", "יום ג׳", "יום ד׳", "יום ה׳", "יום ו׳", "שבת", "יום א׳"],
+        day_month: "%-d %b",
+        month_year: "%B %Y",
+        week_start: Weekday::Sun,
+    },
+    Locale {
+        codes: &["ar", "ara"],
+        months: ["يناير", "فبراير", "مارس", "أبريل", "مايو", "يونيو", "يوليو", "أغسطس", "سبتمبر", "أكتوبر", "نوفمبر", "ديسمبر"],
+        months_short: ["يناير", "فبراير", "مارس", "أبريل", "مايو", "يونيو", "يوليو", "أغسطس", "سبتمبر", "أكتوبر", "نوفمبر", "ديسمبر"],
+        weekdays: ["الاثنين", "الثلاثاء", "الأربعاء", "الخميس", "الجمعة", "السبت", "الأحد"],
+        weekdays_short: ["الاثنين", "الثلاثاء", "الأربعاء", "الخميس", "الجمعة", "السبت", "الأحد"],
+        day_month: "%-d %b",
+        month_year: "%B %Y",
+        week_start: Weekday::Sat,
+    },
+];
+
+impl Locale {
+    /// Looks the language up by ISO 639-1/639-2 code, region suffixes (`de-AT`, `pt_BR`) are
+    /// ignored. Unknown languages fall back to English.
+    pub fn get(code: &str) -> &'static Locale {
+        let language = code
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        LOCALES
+            .iter()
+            .find(|it| it.codes.contains(&language.as_str()))
+            .unwrap_or(&LOCALES[0])
+    }
+
+    pub fn month(&self, date: &NaiveDateTime, short: bool) -> &'static str {
+        let index = date.month0() as usize;
+        if short {
+            self.months_short[index]
+        } else {
+            self.months[index]
+        }
+    }
+
+    pub fn weekday(&self, date: &NaiveDateTime, short: bool) -> &'static str {
+        let index = date.weekday().num_days_from_monday() as usize;
+        if short {
+            self.weekdays_short[index]
+        } else {
+            self.weekdays[index]
+        }
+    }
+
+    /// chrono `strftime` formatting with `%a`, `%A`, `%b`, `%h` and `%B` in this language.
+    /// Patterns chrono cannot write, like `%Q` or `%z`, come back as they are.
+    pub fn format(&self, date: &NaiveDateTime, pattern: &str) -> String {
+        let mut localized = String::with_capacity(pattern.len());
+        let mut chars = pattern.chars();
+        while let Some(char) = chars.next() {
+            if char != '%' {
+                localized.push(char);
+                continue;
+            }
+            match chars.next() {
+                Some('a') => localized.push_str(self.weekday(date, true)),
+                Some('A') => localized.push_str(self.weekday(date, false)),
+                Some('b') | Some('h') => localized.push_str(self.month(date, true)),
+                Some('B') => localized.push_str(self.month(date, false)),
+                Some(other) => {
+                    localized.push('%');
+                    localized.push(other);
+                }
+                None => localized.push('%'),
+            }
+        }
+        let mut value = String::new();
+        match write!(value, "{}", date.format(&localized)) {
+            Ok(()) => value,
+            Err(_) => {
+                log::warn!("invalid date pattern {}", pattern);
+                pattern.to_owned()
+            }
+        }
+    }
+}
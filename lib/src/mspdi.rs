@@ -0,0 +1,281 @@
+//! Microsoft Project XML (MSPDI) files, the format MS Project saves as `.xml`.
+//!
+//! Tasks map to tasks, summary tasks to projects holding the tasks outlined below them,
+//! milestones to milestones. Predecessor links of any type become dependencies. Resources,
+//! calendars and split parts are left out.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use chrono::NaiveDateTime;
+
+use crate::schemas::{Task, TaskType};
+use crate::time_zone::{self, DisplayTimeZone};
+
+/// Format of the dates in the file, wall clock times of the project.
+const DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+/// Reads the tasks of a file, dates are written in `fmt` and `tz` like task dates. The ids are
+/// the unique ids of the file, the display order follows the file.
+pub fn import(xml: &str, fmt: &str, tz: &DisplayTimeZone) -> Result<Vec<Task>, String> {
+    let doc = roxmltree::Document::parse(xml).map_err(|err| err.to_string())?;
+    let project = doc.root_element();
+    if project.tag_name().name() != "Project" {
+        return Err("not a Microsoft Project XML file".to_owned());
+    }
+    let mut tasks: Vec<Task> = vec![];
+    // ids and outline levels of the tasks above, down to the current one
+    let mut outline: Vec<(usize, String)> = vec![];
+    for node in child(project, "Tasks")
+        .into_iter()
+        .flat_map(|it| it.children())
+        .filter(|it| it.tag_name().name() == "Task")
+    {
+        let level = text(node, "OutlineLevel").and_then(|it| it.parse().ok()).unwrap_or(1);
+        // the project summary task is level 0, blank rows are null
+        if level == 0 || flag(node, "IsNull") {
+            continue;
+        }
+        let Some(uid) = text(node, "UID") else {
+            continue;
+        };
+        while outline.last().is_some_and(|it| it.0 >= level) {
+            outline.pop();
+        }
+        let date = |name| {
+            let date = time_zone::parse_date(text(node, name)?, DATE_FORMAT, tz);
+            Some(time_zone::format_date(date.map_err(|err| log::warn!("{}", err)).ok()?, fmt, tz))
+        };
+        let dependencies: Vec<String> = node
+            .children()
+            .filter(|it| it.tag_name().name() == "PredecessorLink")
+            .filter_map(|it| text(it, "PredecessorUID").map(str::to_owned))
+            .collect();
+        tasks.push(Task {
+            id: Some(uid.to_owned()),
+            name: text(node, "Name").map(str::to_owned),
+            start: date("Start"),
+            end: date("Finish"),
+            progress: text(node, "PercentComplete").and_then(|it| it.parse().ok()),
+            type_: Some(if flag(node, "Summary") {
+                TaskType::Project
+            } else if flag(node, "Milestone") {
+                TaskType::Milestone
+            } else {
+                TaskType::Task
+            }),
+            project: outline.last().map(|it| it.1.clone()),
+            dependencies: (!dependencies.is_empty()).then_some(dependencies),
+            display_order: Some((tasks.len() + 1) as f64),
+            ..Task::default()
+        });
+        outline.push((level, uid.to_owned()));
+    }
+    // links to the project summary task or to blank rows
+    let ids: Vec<Option<String>> = tasks.iter().map(|it| it.id.clone()).collect();
+    for task in &mut tasks {
+        if let Some(dependencies) = &mut task.dependencies {
+            dependencies.retain(|it| ids.contains(&Some(it.clone())));
+        }
+    }
+    Ok(tasks)
+}
+
+/// Writes the tasks as a file, dates read in `fmt` and `tz` like task dates. Tasks are listed
+/// in display order, projects outline the tasks they hold. Tasks with dates start no earlier
+/// than they do, so MS Project keeps them in place.
+pub fn export(tasks: &[Task], fmt: &str, tz: &DisplayTimeZone) -> String {
+    let task_dates: Vec<Vec<(NaiveDateTime, NaiveDateTime)>> =
+        tasks.iter().map(|it| it.segment_dates(fmt, tz)).collect();
+    let order = crate::sorted(tasks, &task_dates, None, fmt, tz);
+    let (wbs, levels) = crate::outline(tasks, &order);
+    let uids = uids(tasks, &order);
+    let ids: HashMap<&str, usize> = order
+        .iter()
+        .filter_map(|i| Some((tasks[*i].id.as_deref()?, uids[*i])))
+        .collect();
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n");
+    xml.push_str("<Project xmlns=\"http://schemas.microsoft.com/project\">\n");
+    xml.push_str("  <Tasks>\n");
+    for (row, &i) in order.iter().enumerate() {
+        let task = &tasks[i];
+        xml.push_str("    <Task>\n");
+        let mut element = |name: &str, value: &str| {
+            let _ = writeln!(xml, "      <{}>{}</{}>", name, escape(value), name);
+        };
+        element("UID", &uids[i].to_string());
+        element("ID", &(row + 1).to_string());
+        element("Name", task.name.as_deref().unwrap_or_default());
+        element("OutlineNumber", &wbs[i]);
+        element("OutlineLevel", &(levels[i] + 1).to_string());
+        let start = task_dates[i].iter().map(|it| it.0).min();
+        let end = task_dates[i].iter().map(|it| it.1).max();
+        if let (Some(start), Some(end)) = (start, end) {
+            let start = time_zone::format_date(start, DATE_FORMAT, tz);
+            element("Start", &start);
+            element("Finish", &time_zone::format_date(end, DATE_FORMAT, tz));
+            let minutes = task.work_time(fmt, tz).num_minutes();
+            element("Duration", &format!("PT{}H{}M0S", minutes / 60, minutes % 60));
+            if task.type_ != Some(TaskType::Project) {
+                // start no earlier than
+                element("ConstraintType", "4");
+                element("ConstraintDate", &start);
+            }
+        }
+        let flag = |value: bool| if value { "1" } else { "0" };
+        element("Milestone", flag(task.type_ == Some(TaskType::Milestone)));
+        element("Summary", flag(task.type_ == Some(TaskType::Project)));
+        if let Some(progress) = task.progress {
+            element("PercentComplete", &progress.round().clamp(0.0, 100.0).to_string());
+        }
+        for uid in task.dependencies.iter().flatten().filter_map(|it| ids.get(it.as_str())) {
+            xml.push_str("      <PredecessorLink>\n");
+            let _ = writeln!(xml, "        <PredecessorUID>{}</PredecessorUID>", uid);
+            // finish to start
+            xml.push_str("        <Type>1</Type>\n");
+            xml.push_str("      </PredecessorLink>\n");
+        }
+        xml.push_str("    </Task>\n");
+    }
+    xml.push_str("  </Tasks>\n");
+    xml.push_str("</Project>\n");
+    xml
+}
+
+/// Unique id of each task in the file: its id when that is a number no other task has, so ids
+/// survive a round trip. The others are numbered on from the highest one in `order`.
+fn uids(tasks: &[Task], order: &[usize]) -> Vec<usize> {
+    let numbers: Vec<Option<usize>> = tasks
+        .iter()
+        .map(|task| {
+            let id = task.id.as_deref()?;
+            id.parse().ok().filter(|it: &usize| *it > 0 && it.to_string() == id)
+        })
+        .collect();
+    let unique = |number: usize| numbers.iter().filter(|it| **it == Some(number)).count() == 1;
+    let mut uids: Vec<usize> =
+        numbers.iter().map(|it| it.filter(|it| unique(*it)).unwrap_or(0)).collect();
+    let mut next = uids.iter().max().copied().unwrap_or_default();
+    for &i in order {
+        if uids[i] == 0 {
+            next += 1;
+            uids[i] = next;
+        }
+    }
+    uids
+}
+
+/// First child element with the name, in any namespace.
+fn child<'a, 'input>(
+    node: roxmltree::Node<'a, 'input>,
+    name: &str,
+) -> Option<roxmltree::Node<'a, 'input>> {
+    node.children().find(|it| it.tag_name().name() == name)
+}
+
+/// Trimmed text of the child element with the name, if not empty.
+fn text<'a>(node: roxmltree::Node<'a, '_>, name: &str) -> Option<&'a str> {
+    child(node, name)?.text().map(str::trim).filter(|it| !it.is_empty())
+}
+
+/// Whether the child element with the name is set, as `1` or `true`.
+fn flag(node: roxmltree::Node, name: &str) -> bool {
+    matches!(text(node, name), Some("1" | "true"))
+}
+
+/// Text content with the markup characters escaped.
+fn escape(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, type_: TaskType, start: &str, end: &str) -> Task {
+        Task {
+            id: Some(id.to_owned()),
+            name: Some(format!("Task {} & co", id)),
+            start: Some(start.to_owned()),
+            end: Some(end.to_owned()),
+            type_: Some(type_),
+            ..Task::default()
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let fmt = "%Y-%m-%d %H:%M";
+        let tz = DisplayTimeZone::Utc;
+        let mut tasks = vec![
+            task("10", TaskType::Project, "2024-01-01 08:00", "2024-01-05 17:00"),
+            task("3", TaskType::Task, "2024-01-01 08:00", "2024-01-03 17:00"),
+            task("design", TaskType::Task, "2024-01-04 08:00", "2024-01-05 17:00"),
+            task("7", TaskType::Milestone, "2024-01-05 17:00", "2024-01-05 17:00"),
+        ];
+        tasks[1].project = Some("10".to_owned());
+        tasks[1].progress = Some(50.0);
+        tasks[2].project = Some("10".to_owned());
+        tasks[2].dependencies = Some(vec!["3".to_owned()]);
+        tasks[3].dependencies = Some(vec!["10".to_owned(), "design".to_owned()]);
+        for (order, task) in tasks.iter_mut().enumerate() {
+            task.display_order = Some((order + 1) as f64);
+        }
+
+        let xml = export(&tasks, fmt, &tz);
+        assert!(xml.contains("<OutlineLevel>2</OutlineLevel>"));
+        let imported = import(&xml, fmt, &tz).unwrap();
+
+        // numeric ids stay, the others are numbered after them
+        let ids: Vec<_> = imported.iter().map(|it| it.id.clone().unwrap()).collect();
+        assert_eq!(ids, ["10", "3", "11", "7"]);
+        let projects: Vec<_> = imported.iter().map(|it| it.project.clone()).collect();
+        assert_eq!(projects, [None, Some("10".to_owned()), Some("10".to_owned()), None]);
+        let dependencies: Vec<_> = imported.iter().map(|it| it.dependencies.clone()).collect();
+        assert_eq!(
+            dependencies,
+            [
+                None,
+                None,
+                Some(vec!["3".to_owned()]),
+                Some(vec!["10".to_owned(), "11".to_owned()]),
+            ]
+        );
+        let types: Vec<_> = imported.iter().map(|it| it.type_.clone().unwrap()).collect();
+        assert_eq!(types, [TaskType::Project, TaskType::Task, TaskType::Task, TaskType::Milestone]);
+        let progress: Vec<_> = imported.iter().map(|it| it.progress).collect();
+        assert_eq!(progress, [None, Some(50.0), None, None]);
+        for (task, imported) in tasks.iter().zip(&imported) {
+            assert_eq!(task.name, imported.name);
+            assert_eq!((&task.start, &task.end), (&imported.start, &imported.end));
+        }
+
+        // the file ids of imported tasks survive another round trip
+        let again = import(&export(&imported, fmt, &tz), fmt, &tz).unwrap();
+        assert_eq!(again, imported);
+    }
+
+    #[test]
+    fn duplicate_ids_are_renumbered() {
+        let tz = DisplayTimeZone::Utc;
+        let fmt = "%Y-%m-%d";
+        let tasks = vec![
+            task("2", TaskType::Task, "2024-01-01", "2024-01-02"),
+            task("2", TaskType::Task, "2024-01-01", "2024-01-02"),
+            task("05", TaskType::Task, "2024-01-01", "2024-01-02"),
+            task("4", TaskType::Task, "2024-01-01", "2024-01-02"),
+        ];
+        let imported = import(&export(&tasks, fmt, &tz), fmt, &tz).unwrap();
+        let ids: Vec<_> = imported.iter().map(|it| it.id.clone().unwrap()).collect();
+        assert_eq!(ids, ["5", "6", "7", "4"]);
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        let tz = DisplayTimeZone::Utc;
+        assert!(import("<Tasks/>", "%Y-%m-%d", &tz).is_err());
+        assert!(import("<Project>", "%Y-%m-%d", &tz).is_err());
+    }
+}
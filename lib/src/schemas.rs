@@ -3,6 +3,9 @@ use serde::{Deserialize, Serialize};
 use yew::Properties;
 
 use super::macros;
+use super::theme::ThemePreset;
+use super::time_zone::{self, DisplayTimeZone};
+use super::view_mode::{CustomViewMode, Scale, ScaleOptions, TimeScale};
 
 #[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
 pub enum BarMoveAction {
@@ -56,6 +59,12 @@ pub struct BarTask {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "typeInternal")]
     pub type_internal: Option<TaskTypeInternal>,
+    #[doc = " Laid out work segments of a split task"]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub segments: Option<Vec<BarSegment>>,
+    #[doc = " Not matching the quick search"]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimmed: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "x1")]
     pub x_1: Option<f64>,
@@ -77,6 +86,8 @@ impl BarTask {
      index: f64 => Some(index),
      progress_width: f64  => Some(progress_width),
      progress_x: f64 => Some(progress_x),
+     segments: Option<Vec<BarSegment>> => segments,
+     dimmed: bool => Some(dimmed),
      styles: BarTaskStyles => Some(styles),
      type_internal: TaskTypeInternal => Some(type_internal),
      x_1: f64  => Some(x_1),
@@ -86,6 +97,19 @@ impl BarTask {
     }
 }
 
+/// Work segment of a split task on the chart.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct BarSegment {
+    #[serde(rename = "x1")]
+    pub x_1: f64,
+    #[serde(rename = "x2")]
+    pub x_2: f64,
+    #[serde(rename = "progressX")]
+    pub progress_x: f64,
+    #[serde(rename = "progressWidth")]
+    pub progress_width: f64,
+}
+
 #[derive(Clone, PartialEq, Eq, Debug, Default, Deserialize, Serialize)]
 pub struct DateSetup {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -96,9 +120,15 @@ pub struct DateSetup {
 }
 #[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
 pub struct DisplayOption {
-    #[doc = " Specifies the month name language. Able formats: ISO 639-2, Java Locale"]
+    #[doc = " Specifies the month and weekday name language. Able formats: ISO 639-1, ISO 639-2,"]
+    #[doc = " with an optional region (`de-AT`)"]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub locale: Option<String>,
+    #[doc = " chrono format of the task dates, `%Y-%m-%d %H:%M:%S` by default"]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "dateFormat")]
+    pub date_format: Option<String>,
+    #[doc = " Empty columns added before the first and after the last task, 1 by default."]
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "preStepsCount")]
     pub pre_steps_count: Option<f64>,
@@ -110,6 +140,223 @@ pub struct DisplayOption {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "viewMode")]
     pub view_mode: Option<ViewMode>,
+    #[doc = " Time zone of the chart: `local` (default), `UTC`, an offset like `+02:00` or an IANA"]
+    #[doc = " name like `Europe/Berlin`. Task dates without an offset are read in it."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "timeZone")]
+    pub time_zone: Option<String>,
+    #[doc = " First day of the week columns, e.g. `Mon` or `sunday`. Follows `locale` by default."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "weekStart")]
+    pub week_start: Option<chrono::Weekday>,
+    #[doc = " Column length of the `Minute` view mode in minutes, 15 by default."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "minuteStep")]
+    pub minute_step: Option<u32>,
+    #[doc = " Column length of the `Sprint` view mode in weeks, 2 by default."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "sprintWeeks")]
+    pub sprint_weeks: Option<u32>,
+    #[doc = " Start date of any sprint, the week of the first task by default."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "sprintAnchor")]
+    pub sprint_anchor: Option<String>,
+    #[doc = " Ctrl+wheel and pinch zoom, enabled by default."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub zoom: Option<bool>,
+    #[doc = " Rows and columns rendered beyond the visible part of the chart, 10 by default."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overscan: Option<usize>,
+    #[doc = " Dated lines and shaded ranges drawn across the grid."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub markers: Option<Vec<Marker>>,
+    #[doc = " Calendar header label templates per view mode."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "calendarLabels")]
+    pub calendar_labels: Option<Vec<CalendarLabel>>,
+    #[doc = " Month the fiscal year starts in, from 1 to 12. January by default."]
+    #[doc = " Used by the fiscal view modes and the fiscal calendar labels."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "fiscalYearStartMonth")]
+    pub fiscal_year_start_month: Option<u32>,
+    #[doc = " Final say on every calendar header label, gets the label built from the templates."]
+    #[serde(skip)]
+    pub calendar_formatter: Option<yew::Callback<CalendarLabelCtx, String>>,
+    #[doc = " Groups the rows by `project`, `assignee` or a key of `Task.fields`, each group under"]
+    #[doc = " a collapsible header row."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "groupBy")]
+    pub group_by: Option<String>,
+    #[doc = " Packs the tasks of each group, or of each project without `groupBy`, into as few rows"]
+    #[doc = " as they fit in without overlapping."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub compact: Option<bool>,
+    #[doc = " How the table shows dates: `date` for the day only, `relative` like `in 3 days`,"]
+    #[doc = " or a format like `dateFormat`. As the task dates are written by default."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "tableDateFormat")]
+    pub table_date_format: Option<String>,
+    #[doc = " Columns of the task table in their order, `name`, `start`, `end` and `duration` by"]
+    #[doc = " default."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub columns: Option<Vec<TableColumn>>,
+    #[doc = " Tasks to show, the others are left out of both the table and the chart."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<TaskFilter>,
+    #[doc = " Shows a search box, tasks not matching the search are dimmed or hidden."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "quickSearch")]
+    pub quick_search: Option<QuickSearch>,
+}
+#[doc = " Conditions a task has to meet to be shown, all of the given ones."]
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct TaskFilter {
+    #[doc = " Whether the task is past its end and not done"]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub late: Option<bool>,
+    #[doc = " Whether the task is on the critical path, the chain of dependencies without slack"]
+    #[doc = " up to the end of the schedule"]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub critical: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "assignedTo")]
+    pub assigned_to: Option<String>,
+    #[serde(skip)]
+    pub predicate: Option<yew::Callback<Task, bool>>,
+}
+impl TaskFilter {
+    macros::setters! {
+        late: bool => Some(late),
+        critical: bool => Some(critical),
+        assigned_to: String => Some(assigned_to),
+        predicate: yew::Callback<Task, bool> => Some(predicate),
+    }
+}
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Deserialize, Serialize)]
+pub enum QuickSearch {
+    #[default]
+    #[serde(rename = "dim")]
+    Dim,
+    #[serde(rename = "hide")]
+    Hide,
+}
+#[doc = " Column of the task table. `id` picks a built-in column: `name`, `start`, `end`,"]
+#[doc = " `duration`, `progress`, `type`, `dependencies`, `assignee` or `wbs`, the outline number"]
+#[doc = " like `1.2.3` from the project hierarchy. Any other column shows `Task.fields` of"]
+#[doc = " `field`, or of `id` itself."]
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct TableColumn {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>,
+    #[doc = " CSS width, `listCellWidth` by default"]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub align: Option<ColumnAlign>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<bool>,
+    #[doc = " Cell text of a task, replaces the built-in value."]
+    #[serde(skip)]
+    pub value: Option<yew::Callback<Task, String>>,
+}
+impl TableColumn {
+    pub fn new(id: impl Into<String>, header: impl Into<String>) -> Self {
+        TableColumn {
+            id: id.into(),
+            header: Some(header.into()),
+            ..TableColumn::default()
+        }
+    }
+
+    /// Columns of a table without `columns`.
+    pub fn defaults() -> Vec<TableColumn> {
+        vec![
+            TableColumn::new("name", "Name"),
+            TableColumn::new("start", "From"),
+            TableColumn::new("end", "To"),
+            TableColumn::new("duration", "Duration"),
+        ]
+    }
+
+    macros::setters! {
+        width: String => Some(width),
+        align: ColumnAlign => Some(align),
+        field: String => Some(field),
+        hidden: bool => Some(hidden),
+        value: yew::Callback<Task, String> => Some(value),
+    }
+}
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Deserialize, Serialize)]
+pub enum ColumnAlign {
+    #[default]
+    #[serde(rename = "start")]
+    Start,
+    #[serde(rename = "center")]
+    Center,
+    #[serde(rename = "end")]
+    End,
+}
+#[doc = " Templates of the calendar header rows. A template is a chrono format string, month and"]
+#[doc = " weekday names (`%b`, `%B`, `%a`, `%A`) follow `locale`. It also expands `{week}`"]
+#[doc = " (ISO week), `{quarter}`, `{sprint}`, `{fiscal_quarter}`, `{fiscal_year}`,"]
+#[doc = " `{fiscal_year_short}`, `{column}` (1-based column number) and the locale patterns"]
+#[doc = " `{day_month}` and `{month_year}`, e.g. `Q{fiscal_quarter} FY{fiscal_year_short}`."]
+#[derive(Clone, PartialEq, Eq, Debug, Default, Deserialize, Serialize)]
+pub struct CalendarLabel {
+    #[serde(default)]
+    #[serde(rename = "viewMode")]
+    pub view_mode: ViewMode,
+    #[doc = " Name of a custom view mode the templates are for, instead of `viewMode`"]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bottom: Option<String>,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CalendarRow {
+    Top,
+    Bottom,
+}
+#[derive(Clone, PartialEq, Debug)]
+pub struct CalendarLabelCtx {
+    #[doc = " First date of the labelled column, in the display time zone."]
+    pub date: NaiveDateTime,
+    pub view_mode: ViewMode,
+    pub row: CalendarRow,
+    #[doc = " Column index of the label."]
+    pub index: usize,
+    #[doc = " Label built from the template."]
+    pub label: String,
+}
+#[derive(Clone, PartialEq, Eq, Debug, Default, Deserialize, Serialize)]
+pub enum MarkerDash {
+    #[default]
+    #[serde(rename = "solid")]
+    Solid,
+    #[serde(rename = "dashed")]
+    Dashed,
+    #[serde(rename = "dotted")]
+    Dotted,
+}
+#[derive(Clone, PartialEq, Eq, Debug, Default, Deserialize, Serialize)]
+pub struct Marker {
+    #[doc = " Date of the line, or start of the range when `end` is set."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    #[doc = " End of the shaded range."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dash: Option<MarkerDash>,
 }
 #[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
 pub struct EventOption {
@@ -146,6 +393,53 @@ pub struct EventOption {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "timeStep")]
     pub time_step: Option<f64>,
+    #[doc = " Invokes with the task after it was changed on the chart, e.g. split or a segment moved."]
+    #[serde(skip)]
+    pub on_task_change: Option<yew::Callback<Task>>,
+    #[doc = " Decides on every edit in the chart or the table before it is applied, `Err` rejects"]
+    #[doc = " it with the message."]
+    #[serde(skip)]
+    pub validate_task_change: Option<yew::Callback<TaskChange, Result<(), String>>>,
+    #[doc = " Invokes with all tasks after a row was dragged to another place, `displayOrder`"]
+    #[doc = " numbering them in their new order."]
+    #[serde(skip)]
+    pub on_reorder: Option<yew::Callback<Vec<Task>>>,
+    #[doc = " Invokes with all widths the user set after resizing a table column or the task list."]
+    #[serde(skip)]
+    pub on_table_resize: Option<yew::Callback<TableWidths>>,
+}
+#[doc = " Task before and after an edit."]
+#[derive(Clone, PartialEq, Debug)]
+pub struct TaskChange {
+    pub original: Task,
+    pub changed: Task,
+}
+#[doc = " Task row dropped on another one in the table, by their task indices."]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RowDrop {
+    pub task: usize,
+    pub target: usize,
+    pub place: DropPlace,
+}
+#[doc = " Where a dropped row goes, `Into` makes it the last task of the target project."]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DropPlace {
+    Before,
+    After,
+    Into,
+}
+#[doc = " Widths set by resizing, in px. Feed them back as `TableColumn.width` and `tableWidth`."]
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct TableWidths {
+    pub columns: ::std::collections::BTreeMap<String, f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table: Option<f64>,
+}
+#[doc = " Resize of a table column by its id, or of the whole task list."]
+#[derive(Clone, PartialEq, Debug)]
+pub enum TableResize {
+    Column(String, f64),
+    Table(f64),
 }
 #[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
 pub enum GanttContentMoveAction {
@@ -194,21 +488,6 @@ pub struct GanttProps {
     #[serde(flatten)]
     pub style_option: StylingOption,
 }
-#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
-pub struct PartialHeaderHeightNumberRowWidthStringFontFamilyStringFontSizeString {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "fontFamily")]
-    pub font_family: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "fontSize")]
-    pub font_size: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "headerHeight")]
-    pub header_height: Option<f64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "rowWidth")]
-    pub row_width: Option<String>,
-}
 #[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize, Properties)]
 pub struct TableProps {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -227,11 +506,71 @@ pub struct TableProps {
     pub row_width: Option<String>,
     pub header_height: f64,
     pub gantt_height: f64,
+    pub rtl: bool,
+    #[doc = " Rendered rows, the others are replaced by spacers."]
+    #[serde(skip)]
+    pub visible_rows: std::ops::Range<usize>,
+    #[doc = " Body of the table, scrolled along with the chart."]
+    #[serde(skip)]
+    pub body_ref: yew::NodeRef,
+    #[doc = " Scroll container of the chart, the table forwards its wheel events to it."]
+    #[serde(skip)]
+    pub scroll_ref: yew::NodeRef,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "selectedTaskId")]
     pub selected_task_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tasks: Option<Vec<Task>>,
+    #[serde(skip)]
+    pub rows: Vec<Row>,
+    #[serde(skip)]
+    pub columns: Vec<TableColumn>,
+    #[doc = " Width of the task list in px"]
+    #[serde(skip)]
+    pub table_width: Option<f64>,
+    #[serde(skip)]
+    pub on_resize: Option<yew::Callback<TableResize>>,
+    #[doc = " Invokes with the index and the task after an accepted edit"]
+    #[serde(skip)]
+    pub on_task_edit: Option<yew::Callback<(usize, Task)>>,
+    #[serde(skip)]
+    pub validate_task_change: Option<yew::Callback<TaskChange, Result<(), String>>>,
+    #[doc = " Parse format and time zone of the task dates"]
+    #[serde(skip)]
+    pub date_format: String,
+    #[doc = " Display style of the dates, `tableDateFormat`"]
+    #[serde(skip)]
+    pub display_date_format: Option<String>,
+    #[serde(skip)]
+    pub time_zone: DisplayTimeZone,
+    #[doc = " Invokes with the key of a group header clicked to collapse or expand it."]
+    #[serde(skip)]
+    pub on_group_toggle: Option<yew::Callback<String>>,
+    #[doc = " Column the rows are sorted by, descending for `true`"]
+    #[serde(skip)]
+    pub sort: Option<(String, bool)>,
+    #[doc = " Invokes with the id of a column header clicked to sort by it."]
+    #[serde(skip)]
+    pub on_sort: Option<yew::Callback<String>>,
+    #[doc = " Tasks not matching the quick search"]
+    #[serde(skip)]
+    pub dimmed: std::collections::HashSet<usize>,
+    #[doc = " Outline number and depth in the project hierarchy of each task"]
+    #[serde(skip)]
+    pub wbs: Vec<String>,
+    #[serde(skip)]
+    pub levels: Vec<usize>,
+    #[doc = " Invokes with the index of a task whose row is clicked."]
+    #[serde(skip)]
+    pub on_select: Option<yew::Callback<usize>>,
+    #[doc = " Content of the header and of each task row, instead of the built-in cells"]
+    #[serde(skip)]
+    pub header_content: Option<yew::Callback<TableHeaderCtx, yew::Html>>,
+    #[serde(skip)]
+    pub row_content: Option<yew::Callback<TableRowCtx, yew::Html>>,
+    #[doc = " Invokes when a row is dragged onto another one, rows are only dragged unsorted."]
+    #[serde(skip)]
+    pub on_row_drop: Option<yew::Callback<RowDrop>>,
 
     #[doc = " Sets selected task by id"]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -251,139 +590,79 @@ impl TableProps {
         row_width: Option<String> => row_width,
         header_height: f64 => header_height,
         gantt_height: f64 => gantt_height,
+        visible_rows: std::ops::Range<usize> => visible_rows,
+        body_ref: yew::NodeRef => body_ref,
+        scroll_ref: yew::NodeRef => scroll_ref,
+        rtl: bool => rtl,
         selected_task_id: Option<String> => selected_task_id,
         tasks: Option<Vec<Task>> => tasks,
+        rows: Vec<Row> => rows,
+        columns: Vec<TableColumn> => columns,
+        table_width: Option<f64> => table_width,
+        on_resize: Option<yew::Callback<TableResize>> => on_resize,
+        on_task_edit: Option<yew::Callback<(usize, Task)>> => on_task_edit,
+        validate_task_change: Option<yew::Callback<TaskChange, Result<(), String>>>
+            => validate_task_change,
+        date_format: String => date_format,
+        display_date_format: Option<String> => display_date_format,
+        time_zone: DisplayTimeZone => time_zone,
+        on_group_toggle: Option<yew::Callback<String>> => on_group_toggle,
+        sort: Option<(String, bool)> => sort,
+        on_sort: Option<yew::Callback<String>> => on_sort,
+        dimmed: std::collections::HashSet<usize> => dimmed,
+        on_row_drop: Option<yew::Callback<RowDrop>> => on_row_drop,
+        wbs: Vec<String> => wbs,
+        levels: Vec<usize> => levels,
+        on_select: Option<yew::Callback<usize>> => on_select,
+        header_content: Option<yew::Callback<TableHeaderCtx, yew::Html>> => header_content,
+        row_content: Option<yew::Callback<TableRowCtx, yew::Html>> => row_content,
         
         on_expander_click: Option<::std::collections::BTreeMap<String, serde_json::Value>> => on_expander_click,
         set_selected_task: Option<::std::collections::BTreeMap<String, serde_json::Value>> => set_selected_task,
     }
 }
 
-#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
-pub struct PartialTaskTaskFontSizeStringFontFamilyString {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "fontFamily")]
-    pub font_family: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "fontSize")]
-    pub font_size: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub task: Option<Task>,
-}
-#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
-pub struct ReactFCHeaderHeightNumberRowWidthStringFontFamilyStringFontSizeString {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "contextTypes")]
-    pub context_types: Option<ValidationMapAny>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "defaultProps")]
-    pub default_props:
-        Option<PartialHeaderHeightNumberRowWidthStringFontFamilyStringFontSizeString>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "displayName")]
-    pub display_name: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "propTypes")]
-    pub prop_types: Option<FontFamilyStringFontSizeString>,
-}
-#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
-pub struct OnExpanderClickTaskTaskVoid {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "contextTypes")]
-    pub context_types: Option<ValidationMapAny>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "defaultProps")]
-    pub default_props: Option<TableProps>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "displayName")]
-    pub display_name: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "propTypes")]
-    pub prop_types: Option<ExpanderClickTaskTaskVoid>,
-}
 
-#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
-pub struct ReactFCTaskTaskFontSizeStringFontFamilyString {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "contextTypes")]
-    pub context_types: Option<ValidationMapAny>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "defaultProps")]
-    pub default_props: Option<PartialTaskTaskFontSizeStringFontFamilyString>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "displayName")]
-    pub display_name: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "propTypes")]
-    pub prop_types: Option<ReactWeakValidationMapTaskTaskFontSizeStringFontFamilyString>,
+#[doc = " What the task list header is rendered from."]
+#[derive(Clone, PartialEq, Debug)]
+pub struct TableHeaderCtx {
+    #[doc = " Shown columns"]
+    pub columns: Vec<TableColumn>,
+    #[doc = " Column the rows are sorted by, descending for `true`"]
+    pub sort: Option<(String, bool)>,
 }
-#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
-pub struct FontFamilyStringFontSizeString {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "fontFamily")]
-    pub font_family: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "fontSize")]
-    pub font_size: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "headerHeight")]
-    pub header_height: Option<f64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "rowWidth")]
-    pub row_width: Option<String>,
-}
-#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
-pub struct ExpanderClickTaskTaskVoid {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "fontFamily")]
-    pub font_family: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "fontSize")]
-    pub font_size: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub locale: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "onExpanderClick")]
-    pub on_expander_click: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "rowHeight")]
-    pub row_height: Option<f64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "rowWidth")]
-    pub row_width: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "selectedTaskId")]
-    pub selected_task_id: Option<String>,
-    #[doc = " Sets selected task by id"]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "setSelectedTask")]
-    pub set_selected_task: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub tasks: Option<Vec<String>>,
+#[doc = " What a task row of the task list is rendered from."]
+#[derive(Clone, PartialEq, Debug)]
+pub struct TableRowCtx {
+    pub task: Task,
+    #[doc = " Index in the tasks"]
+    pub index: usize,
+    #[doc = " Shown columns"]
+    pub columns: Vec<TableColumn>,
+    #[doc = " Outline number and depth in the project hierarchy"]
+    pub wbs: String,
+    pub level: usize,
+    pub selected: bool,
 }
-#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
-pub struct ReactWeakValidationMapTaskTaskFontSizeStringFontFamilyString {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "fontFamily")]
-    pub font_family: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "fontSize")]
-    pub font_size: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub task: Option<Task>,
+#[doc = " What the tooltip of a bar is rendered from."]
+#[derive(Clone, PartialEq, Debug)]
+pub struct TooltipCtx {
+    pub task: Task,
+    pub font_family: String,
+    pub font_size: String,
 }
 #[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
 pub struct StylingOption {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "TaskListHeader")]
-    pub task_list_header:
-        Option<ReactFCHeaderHeightNumberRowWidthStringFontFamilyStringFontSizeString>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "TaskListTable")]
-    pub task_list_table: Option<OnExpanderClickTaskTaskVoid>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    #[serde(rename = "TooltipContent")]
-    pub tooltip_content: Option<ReactFCTaskTaskFontSizeStringFontFamilyString>,
+    #[doc = " Renders the content of the task list header instead of the column headers, columns"]
+    #[doc = " are then no longer sorted or resized from the header."]
+    #[serde(skip)]
+    pub task_list_header: Option<yew::Callback<TableHeaderCtx, yew::Html>>,
+    #[doc = " Renders the content of each task row of the task list instead of its cells."]
+    #[serde(skip)]
+    pub task_list_table: Option<yew::Callback<TableRowCtx, yew::Html>>,
+    #[doc = " Renders the content of the tooltip of the bar under the pointer."]
+    #[serde(skip)]
+    pub tooltip_content: Option<yew::Callback<TooltipCtx, yew::Html>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "arrowColor")]
     pub arrow_color: Option<String>,
@@ -413,6 +692,14 @@ pub struct StylingOption {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "columnWidth")]
     pub column_width: Option<f64>,
+    #[doc = " Narrowest column before zooming out switches to a coarser view mode, 20 by default."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "minColumnWidth")]
+    pub min_column_width: Option<f64>,
+    #[doc = " Widest column before zooming in switches to a finer view mode, 300 by default."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "maxColumnWidth")]
+    pub max_column_width: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "fontFamily")]
     pub font_family: Option<String>,
@@ -431,6 +718,10 @@ pub struct StylingOption {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "listCellWidth")]
     pub list_cell_width: Option<String>,
+    #[doc = " Width of the task list in px, as wide as its columns by default."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "tableWidth")]
+    pub table_width: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "milestoneBackgroundColor")]
     pub milestone_background_color: Option<String>,
@@ -455,6 +746,9 @@ pub struct StylingOption {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "todayColor")]
     pub today_color: Option<String>,
+    #[doc = " Colour preset, the colours and fonts set above take precedence over it."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<ThemePreset>,
 }
 #[derive(Clone, PartialEq, Eq, Debug, Default, Deserialize, Serialize)]
 pub struct TaskStyles {
@@ -473,6 +767,8 @@ pub struct TaskStyles {
 }
 #[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
 pub struct Task {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dependencies: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -480,6 +776,9 @@ pub struct Task {
     pub display_order: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub end: Option<String>,
+    #[doc = " Custom fields of the application"]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<::std::collections::BTreeMap<String, serde_json::Value>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "hideChildren")]
     pub hide_children: Option<bool>,
@@ -495,6 +794,10 @@ pub struct Task {
     pub progress: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub project: Option<String>,
+    #[doc = " Work segments of a split task, the gaps between them are no work. `start` and `end`"]
+    #[doc = " span all segments."]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub segments: Option<Vec<TaskSegment>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub start: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -503,6 +806,89 @@ pub struct Task {
     #[serde(rename = "type")]
     pub type_: Option<TaskType>,
 }
+
+impl Task {
+    /// Work periods of the task: its segments in date order, or `start` to `end`. Dates which
+    /// do not parse are skipped.
+    pub fn segment_dates(
+        &self,
+        fmt: &str,
+        tz: &DisplayTimeZone,
+    ) -> Vec<(NaiveDateTime, NaiveDateTime)> {
+        let parse = |value: &Option<String>| {
+            time_zone::parse_date(value.as_deref()?, fmt, tz)
+                .map_err(|err| log::warn!("{}", err))
+                .ok()
+        };
+        let mut dates: Vec<(NaiveDateTime, NaiveDateTime)> = match &self.segments {
+            Some(segments) if !segments.is_empty() => segments
+                .iter()
+                .filter_map(|it| Some((parse(&it.start)?, parse(&it.end)?)))
+                .collect(),
+            _ => parse(&self.start).zip(parse(&self.end)).into_iter().collect(),
+        };
+        dates.sort();
+        dates
+    }
+
+    /// Value of a field by name: `project` (a project is in its own), `assignee` or a key of
+    /// `fields`. Missing and null values are `None`.
+    pub fn field(&self, name: &str) -> Option<String> {
+        match name {
+            "project" if self.type_ == Some(TaskType::Project) => self.id.clone(),
+            "project" => self.project.clone(),
+            "assignee" => self.assignee.clone(),
+            _ => match self.fields.as_ref()?.get(name)? {
+                serde_json::Value::Null => None,
+                serde_json::Value::String(value) => Some(value.clone()),
+                value => Some(value.to_string()),
+            },
+        }
+    }
+
+    /// Sets the work periods, as segments when the task has segments or there are several.
+    /// `start` and `end` span all of them.
+    pub fn set_segment_dates(
+        &mut self,
+        segments: &[(NaiveDateTime, NaiveDateTime)],
+        fmt: &str,
+        tz: &DisplayTimeZone,
+    ) {
+        let (Some(start), Some(end)) = (
+            segments.iter().map(|it| it.0).min(),
+            segments.iter().map(|it| it.1).max(),
+        ) else {
+            return;
+        };
+        if self.segments.is_some() || segments.len() > 1 {
+            self.segments = Some(
+                segments
+                    .iter()
+                    .map(|(start, end)| TaskSegment {
+                        start: Some(time_zone::format_date(*start, fmt, tz)),
+                        end: Some(time_zone::format_date(*end, fmt, tz)),
+                    })
+                    .collect(),
+            );
+        }
+        self.start = Some(time_zone::format_date(start, fmt, tz));
+        self.end = Some(time_zone::format_date(end, fmt, tz));
+    }
+
+    /// Time worked on the task, the gaps between segments do not count.
+    pub fn work_time(&self, fmt: &str, tz: &DisplayTimeZone) -> chrono::Duration {
+        self.segment_dates(fmt, tz)
+            .iter()
+            .fold(chrono::Duration::zero(), |sum, (start, end)| sum + (*end - *start))
+    }
+}
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct TaskSegment {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<String>,
+}
 #[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
 pub enum TaskType {
     #[serde(rename = "milestone")]
@@ -533,106 +919,113 @@ pub enum TaskTypeInternal {
     #[serde(rename = "task")]
     Task,
 }
-pub type ValidationMapAny = ::std::collections::BTreeMap<String, serde_json::Value>;
 #[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize, Default)]
 pub enum ViewMode {
     Day,
+    #[serde(rename = "Fiscal Quarter")]
+    FiscalQuarter,
+    #[serde(rename = "Fiscal Year")]
+    FiscalYear,
     #[serde(rename = "Half Day")]
     HalfDay,
     Hour,
+    Minute,
     #[default]
     Month,
     #[serde(rename = "Quarter Day")]
     QuarterDay,
     QuarterYear,
+    Sprint,
     Week,
     Year,
+    #[doc = " View mode of the application, see [`TimeScale`]."]
+    #[serde(skip)]
+    Custom(CustomViewMode),
 }
 
 impl ViewMode {
+    /// Next (or previous) column boundary, configurable modes use their default settings.
     pub fn get_mod_date(&self, date_time: NaiveDateTime, add: bool) -> NaiveDateTime {
-        match self {
-            ViewMode::Hour => {
-                if add {
-                    date_time.checked_add_signed(chrono::Duration::hours(1))
-                } else {
-                    date_time.checked_sub_signed(chrono::Duration::hours(1))
-                }
-            }
-            ViewMode::QuarterDay => {
-                if add {
-                    date_time.checked_add_signed(chrono::Duration::hours(6))
-                } else {
-                    date_time.checked_sub_signed(chrono::Duration::hours(6))
-                }
-            }
-            ViewMode::HalfDay => {
-                if add {
-                    date_time.checked_add_signed(chrono::Duration::hours(12))
-                } else {
-                    date_time.checked_sub_signed(chrono::Duration::hours(12))
-                }
-            }
-            ViewMode::Day => {
-                if add {
-                    date_time.checked_add_days(chrono::Days::new(1))
-                } else {
-                    date_time.checked_sub_days(chrono::Days::new(1))
-                }
-            }
-            ViewMode::Week => {
-                if add {
-                    date_time.checked_add_days(chrono::Days::new(7))
-                } else {
-                    date_time.checked_sub_days(chrono::Days::new(7))
-                }
-            }
-            ViewMode::Month => {
-                if add {
-                    date_time.checked_add_months(chrono::Months::new(1))
-                } else {
-                    date_time.checked_sub_months(chrono::Months::new(1))
-                }
-            }
-            ViewMode::QuarterYear => {
-                if add {
-                    date_time.checked_add_months(chrono::Months::new(3))
-                } else {
-                    date_time.checked_sub_months(chrono::Months::new(3))
-                }
-            }
-            ViewMode::Year => {
-                if add {
-                    date_time.checked_add_months(chrono::Months::new(12))
-                } else {
-                    date_time.checked_sub_months(chrono::Months::new(12))
-                }
-            }
-        }
-        .unwrap()
+        Scale::new(self.clone(), ScaleOptions::default()).step(date_time, add)
     }
 }
 
+/// Marker placed on the timeline, `x_1 == x_2` for a line.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct GridMarker {
+    pub x_1: f64,
+    pub x_2: f64,
+    pub is_range: bool,
+    pub label: String,
+    pub color: String,
+    pub dash: MarkerDash,
+}
+
+/// Row of the table and the chart.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Row {
+    /// Header of a group, see `DisplayOption::group_by`
+    Group(GroupRow),
+    /// Task of this index
+    Task(usize),
+    /// Tasks sharing a row in compact mode, see `DisplayOption::compact`
+    Lane(LaneRow),
+}
+
+/// Header row of a group of tasks, with a summary bar over all of them.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct GroupRow {
+    pub key: String,
+    pub label: String,
+    /// Indexes of the tasks in the group
+    pub tasks: Vec<usize>,
+    pub collapsed: bool,
+    /// First start and last end of the tasks, as task dates
+    pub start: String,
+    pub end: String,
+    pub x_1: f64,
+    pub x_2: f64,
+}
+
+/// Row of a group packed into lanes.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct LaneRow {
+    /// Group of the lane, as in `GroupRow`
+    pub key: String,
+    pub label: String,
+    /// Position of the lane in its group and number of lanes of the group
+    pub lane: usize,
+    pub lanes: usize,
+    /// Indexes of the tasks in the lane
+    pub tasks: Vec<usize>,
+    /// Number of tasks in the group
+    pub count: usize,
+}
+
 #[derive(Default, Clone, PartialEq, Properties)]
 pub struct GridProps {
     pub tasks: Vec<super::schemas::Task>,
+    pub rows: Vec<Row>,
     pub dates: Vec<chrono::NaiveDateTime>,
     pub svg_width: f64,
     pub row_height: f64,
     pub column_width: f64,
     pub today_color: String,
     pub rtl: bool,
+    pub markers: Vec<GridMarker>,
 }
 
 impl GridProps {
     macros::setters! {
         tasks: Vec<super::schemas::Task> => tasks,
+        rows: Vec<Row> => rows,
         dates: Vec<chrono::NaiveDateTime> => dates,
         svg_width: f64 => svg_width,
         row_height: f64 => row_height,
         column_width: f64 => column_width,
         today_color: String => today_color,
         rtl: bool => rtl,
+        markers: Vec<GridMarker> => markers,
     }
 }
 
@@ -640,7 +1033,12 @@ impl GridProps {
 pub struct CalendarProps {
     pub date_setup: DateSetup,
     pub locale: String,
+    pub labels: Vec<CalendarLabel>,
+    pub formatter: Option<yew::Callback<CalendarLabelCtx, String>>,
+    pub scale: Scale,
     pub view_mode: ViewMode,
+    /// Columns whose labels are rendered
+    pub visible_columns: std::ops::Range<usize>,
     pub rtl: bool,
     pub header_height: f64,
     pub column_width: f64,
@@ -652,7 +1050,11 @@ impl CalendarProps {
     macros::setters! {
         date_setup: DateSetup => date_setup,
         locale: String => locale,
+        labels: Vec<CalendarLabel> => labels,
+        formatter: Option<yew::Callback<CalendarLabelCtx, String>> => formatter,
+        scale: Scale => scale,
         view_mode: ViewMode => view_mode,
+        visible_columns: std::ops::Range<usize> => visible_columns,
         rtl: bool => rtl,
         header_height: f64 => header_height,
         column_width: f64 => column_width,
@@ -678,6 +1080,7 @@ pub struct TaskGanttContentProps {
     pub font_size: String,
     pub font_family: String,
     pub rtl: bool,
+    pub tooltip_content: Option<yew::Callback<TooltipCtx, yew::Html>>,
 }
 
 impl TaskGanttContentProps {
@@ -697,9 +1100,37 @@ impl TaskGanttContentProps {
         rtl: bool => rtl,
         font_family: String => font_family,
         font_size: String => font_size,
+        tooltip_content: Option<yew::Callback<TooltipCtx, yew::Html>> => tooltip_content,
     }
 }
 
+/// Zoom gesture over the chart.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ZoomEvent {
+    /// Column width multiplier
+    pub factor: f64,
+    /// x of the pointer on the chart
+    pub x: f64,
+    /// x of the pointer in the visible part of the chart
+    pub offset: f64,
+}
+
+/// Pointer action on a bar.
+#[derive(Clone, PartialEq, Debug)]
+pub enum BarEvent {
+    /// A segment of a split task is dragged `dx` along the chart, `done` on release.
+    SegmentDrag {
+        task: usize,
+        segment: usize,
+        dx: f64,
+        done: bool,
+    },
+    /// Alt+click splits the task at chart `x`.
+    Split { task: usize, x: f64 },
+    /// A bar is pressed, its task becomes the selected one.
+    Select { task: usize },
+}
+
 #[derive(Properties, Clone, PartialEq)]
 pub struct SvgProps {
     pub grid_props: GridProps,
@@ -707,5 +1138,25 @@ pub struct SvgProps {
     pub bar_props: TaskGanttContentProps,
     pub gantt_height: f64,
     pub scroll_y: f64,
+    /// Horizontal scroll position applied after rendering, ignored when negative
     pub scroll_x: f64,
+    /// Horizontally scrolling container of the chart
+    pub container_ref: yew::NodeRef,
+    pub on_zoom: Option<yew::Callback<ZoomEvent>>,
+    pub visible_rows: std::ops::Range<usize>,
+    pub visible_columns: std::ops::Range<usize>,
+    /// Body of the table, kept at the vertical scroll position of the chart
+    pub table_body_ref: yew::NodeRef,
+    pub on_viewport: yew::Callback<Viewport>,
+    pub on_bar_event: yew::Callback<BarEvent>,
+    pub on_group_toggle: yew::Callback<String>,
+}
+
+/// Visible part of the chart body, in chart coordinates.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Viewport {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
 }
@@ -1,273 +1,156 @@
-use chrono::{Datelike, NaiveDateTime, Utc, NaiveDate, Timelike};
-use yew::{html, Component, Context, Html};
+use std::cell::Cell;
+use std::ops::Range;
+use std::rc::Rc;
 
-use super::schemas::{
-    BarTask, CalendarProps, GridProps, SvgProps, TaskGanttContentProps, TaskTypeInternal, ViewMode,
-};
+use chrono::{Datelike, NaiveDateTime};
+use gloo_events::EventListener;
+use wasm_bindgen::JsCast;
+use yew::{html, Callback, Component, Context, Event, Html, MouseEvent, TouchEvent, WheelEvent};
 
-impl ViewMode {
-    fn get_values(&self, props: CalendarProps) -> (Html, Html) {
-        match self {
-            ViewMode::Hour => {
-                let dates = props.date_setup.dates.clone().unwrap();
-                let height = props.header_height * 0.5;
-                let top: Html = dates
-                    .iter()
-                    .enumerate()
-                    .filter(|(i, date)| *i != 0 && date.date() != dates.get(i - 1).unwrap().date())
-                    .map(|(i, date)| {
-                        let i = i as f64;
-                        // todo: customize ?
-                        let value = format!("{}, {}", date.iso_week().week(), date.format("%d %m"));
-                        let top_position = ((date.hour() - 24) / 2) as f64;
-                        let x_text = props.column_width * (i + top_position);
-                        let x_line = props.column_width * i;
-                        top_calendar(&props, x_line, height, x_text, value)
-                    })
-                    .collect();
+mod text;
 
-                let bottom: Html = dates
-                    .iter()
-                    .enumerate()
-                    .map(|(i, date)| { 
-                        // todo: customize ?                    
-                        let value = format!("{}", date.format("%H"));
-                        let key = format!("{}", date.timestamp());
-                        bottom_calendar(&props, i as f64, value, key)
-                    }).collect();
-
-                (bottom, top)
-            },
-            ViewMode::QuarterDay => {
-                let dates = props.date_setup.dates.clone().unwrap();
-                let height = props.header_height * 0.5;
-                let top: Html = dates
-                    .iter()
-                    .enumerate()
-                    .filter(|(i, date)| *i != 0 && date.date() != dates.get(i - 1).unwrap().date())
-                    .map(|(i, date)| {
-                        let i = i as f64;
-                        // todo: customize ?
-                        let value = format!("{}, {}", date.iso_week().week(), date.format("%d %m"));
-                        let x_text = props.column_width * i + 4.0 * props.column_width * 0.5;
-                        let x_line = props.column_width * i + 4.0 * props.column_width;
-                        top_calendar(&props, x_line, height, x_text, value)
-                    })
-                    .collect();
+use super::locale::Locale;
+use super::schemas::{
+    BarEvent, BarSegment, BarTask, CalendarLabelCtx, CalendarProps, CalendarRow, GridMarker,
+    GridProps, GroupRow, MarkerDash, Row, SvgProps, TaskGanttContentProps, TaskTypeInternal,
+    TooltipCtx, ViewMode, Viewport, ZoomEvent,
+};
+use super::time_zone;
+use super::view_mode::TimeScale;
 
-                let bottom: Html = dates
-                    .iter()
-                    .enumerate()
-                    .map(|(i, date)| { 
-                        // todo: customize ?                    
-                        let value = format!("{}", date.format("%H"));
-                        let key = format!("{}", date.timestamp());
-                        bottom_calendar(&props, i as f64, value, key)
-                    }).collect();
-
-                (bottom, top)
-            },
-            ViewMode::HalfDay => {
-                let dates = props.date_setup.dates.clone().unwrap();
-                let height = props.header_height * 0.5;
-                let top: Html = dates
-                    .iter()
-                    .enumerate()
-                    .filter(|(i, date)| *i != 0 && date.date() != dates.get(i - 1).unwrap().date())
-                    .map(|(i, date)| {
-                        let i = i as f64;
-                        // todo: customize ?
-                        let value = format!("{}, {}", date.iso_week().week(), date.format("%d %m"));
-                        let x_text = props.column_width * i + 2.0 * props.column_width * 0.5;
-                        let x_line = props.column_width * i + 2.0 * props.column_width;
-                        top_calendar(&props, x_line, height, x_text, value)
-                    })
-                    .collect();
+/// Calendar rows of `scale`: one bottom label per column and a top label over every run of
+/// columns `same_top` groups together. Returns the bottom and the top row.
+pub(crate) fn calendar_header<S: TimeScale + ?Sized>(
+    scale: &S,
+    props: &CalendarProps,
+) -> (Html, Html) {
+    let dates = props.date_setup.dates.clone().unwrap_or_default();
+    let height = props.header_height * 0.5;
+    let (top_template, bottom_template) = scale.labels();
+    let templates = props.labels.iter().find(|it| match (&props.view_mode, &it.custom) {
+        (ViewMode::Custom(custom), Some(name)) => custom.name == *name,
+        (_, Some(_)) => false,
+        (view_mode, None) => it.view_mode == *view_mode,
+    });
+    let top_template = templates.and_then(|it| it.top.clone()).unwrap_or(top_template);
+    let bottom_template = templates.and_then(|it| it.bottom.clone()).unwrap_or(bottom_template);
 
-                let bottom: Html = dates
-                    .iter()
-                    .enumerate()
-                    .map(|(i, date)| { 
-                        // todo: customize ?                    
-                        let value = format!("{}", date.format("%H"));
-                        let key = format!("{}", date.timestamp());
-                        bottom_calendar(&props, i as f64, value, key)
-                    }).collect();
-
-                (bottom, top)
-            },
-            ViewMode::Day => {
-                let dates = props.date_setup.dates.clone().unwrap();
-                let height = props.header_height * 0.5;
-                let top: Html = dates
-                    .iter()
-                    .enumerate()
-                    .filter(|(i, date)| i + 1 != dates.len() && date.month() != dates.get(i + 1).unwrap().month())
-                    .map(|(i, date)| {
-                        let i = i as f64;
-                        // todo: customize ?
-                        let value = format!("{}", date.format("%m"));
-                        let year = if date.month() == 12 { date.year() + 1 } else { date.year() };
-                        let date_num = NaiveDate::from_ymd_opt(year, date.month() + 1, 1).unwrap()
-                            .signed_duration_since(NaiveDate::from_ymd_opt(year, date.month(), 1).unwrap()).num_days() as f64;
-
-                        let x_text = props.column_width * (i + 1.0) - date_num * props.column_width * 0.5;
-                        let x_line = props.column_width * (i + 1.0);
-                        top_calendar(&props, x_line, height, x_text, value)
-                    })
-                    .collect();
+    let columns = props.visible_columns.clone();
+    let bottom: Html = dates
+        .iter()
+        .enumerate()
+        .skip(columns.start)
+        .take(columns.len())
+        .map(|(i, date)| {
+            let value = calendar_label(props, CalendarRow::Bottom, i, date, &bottom_template);
+            let key = format!("{}", date.and_utc().timestamp());
+            bottom_calendar(props, i as f64, value, key)
+        })
+        .collect();
 
-                let bottom: Html = dates
-                    .iter()
-                    .enumerate()
-                    .map(|(i, date)| { 
-                        // todo: customize ?                    
-                        let value = format!("{}", date.format("%d"));
-                        let key = format!("{}", date.timestamp());
-                        bottom_calendar(&props, i as f64, value, key)
-                    }).collect();
-
-                (bottom, top)
-            },
-            ViewMode::Week => {
-                let dates = props.date_setup.dates.clone().unwrap();
-                let height = props.header_height * 0.5;
-                let mut week_count = 0.0; // todo ?
-                let top: Html = dates
-                    .iter()
-                    .enumerate()
-                    .filter(|(i, date)| *i == 0 || date.month() != dates.get(i - 1).unwrap().month())
-                    .map(|(i, date)| {
-                        let i = i as f64;
-                        // todo: customize ?
-                        let value = format!("{}", date.format("%Y"));
-                        let x_text = props.column_width * i + week_count * props.column_width;
-                        let x_line = props.column_width * i + week_count * props.column_width * 0.5;
-                        week_count = 0.0;
-                        top_calendar(&props, x_line, height, x_text, value)
-                    })
-                    .collect();
+    let mut groups: Vec<(usize, usize)> = Vec::new();
+    for (i, date) in dates.iter().enumerate() {
+        match groups.last_mut() {
+            Some((_, len)) if scale.same_top(&dates[i - 1], date) => *len += 1,
+            _ => groups.push((i, 1)),
+        }
+    }
+    let top: Html = groups
+        .iter()
+        .filter(|(start, len)| *start < columns.end && start + len > columns.start)
+        .map(|(start, len)| {
+            let date = &dates[*start];
+            let value = calendar_label(props, CalendarRow::Top, *start, date, &top_template);
+            let x_line = props.column_width * (*start as f64);
+            let x_text = x_line + props.column_width * (*len as f64) * 0.5;
+            top_calendar(props, x_line, height, x_text, value)
+        })
+        .collect();
 
-                let bottom: Html = dates
-                    .iter()
-                    .enumerate()
-                    .map(|(i, date)| { 
-                        // todo: customize ?                    
-                        let value = format!("W{}", date.iso_week().week());
-                        let key = format!("{}", date.format("%m, %Y"));
-                        bottom_calendar(&props, i as f64, value, key)
-                    }).collect();
-
-                (bottom, top)
-            },
-            ViewMode::Month => {
-                let dates = props.date_setup.dates.clone().unwrap();
-                let height = props.header_height * 0.5;
-                let top: Html = dates
-                    .iter()
-                    .enumerate()
-                    .filter(|(i, date)| *i == 0 || date.year() != dates.get(i - 1).unwrap().year())
-                    .map(|(i, date)| {
-                        let i = i as f64;
-                        // todo: customize ?
-                        let value = format!("{}", date.format("%Y"));
-                        let x_text = if props.rtl {
-                            (6.0 + i + (date.month() as f64) + 1.0) * props.column_width
-                        } else {
-                            (6.0 + i - (date.month() as f64)) * props.column_width
-                        };
-                        let x_line = props.column_width * i;
-                        top_calendar(&props, x_line, height, x_text, value)
-                    })
-                    .collect();
+    (bottom, top)
+}
 
-                let bottom: Html = dates
-                    .iter()
-                    .enumerate()
-                    .map(|(i, date)| { 
-                        // todo: customize ?                    
-                        let value = format!("{}", date.format("%m"));
-                        let key = format!("{}", date.format("%m, %Y"));
-                        bottom_calendar(&props, i as f64, value, key)
-                    }).collect();
-
-                (bottom, top)
-            },
-            ViewMode::QuarterYear => {
-                let dates = props.date_setup.dates.clone().unwrap();
-                let height = props.header_height * 0.5;
-                let top: Html = dates
-                    .iter()
-                    .enumerate()
-                    .filter(|(i, date)| *i == 0 || date.year() != dates.get(i - 1).unwrap().year())
-                    .map(|(i, date)| {
-                        let i = i as f64;
-                        // todo: customize ?
-                        let value = format!("{}", date.format("%Y"));
-                        let x_text = if props.rtl {
-                            (6.0 + i + (date.month() as f64) + 1.0) * props.column_width
-                        } else {
-                            (6.0 + i - (date.month() as f64)) * props.column_width
-                        };
-                        let x_line = props.column_width * i;
-                        top_calendar(&props, x_line, height, x_text, value)
-                    })
-                    .collect();
+/// Expands a label template, then lets the `formatter` callback adjust the result.
+fn calendar_label(
+    props: &CalendarProps,
+    row: CalendarRow,
+    index: usize,
+    date: &NaiveDateTime,
+    template: &str,
+) -> String {
+    let locale = Locale::get(&props.locale);
+    let scale = &props.scale;
+    let fiscal_year = scale.fiscal_year(date);
+    // a week column not starting on Monday is numbered by the ISO week of its middle day
+    let week = match props.view_mode {
+        ViewMode::Week => (*date + chrono::Duration::days(3)).iso_week().week(),
+        _ => date.iso_week().week(),
+    };
+    let pattern = template
+        .replace("{day_month}", locale.day_month)
+        .replace("{month_year}", locale.month_year)
+        .replace("{week}", &week.to_string())
+        .replace("{quarter}", &date.month().div_ceil(3).to_string())
+        .replace("{sprint}", &scale.sprint(date).to_string())
+        .replace("{fiscal_quarter}", &scale.fiscal_quarter(date).to_string())
+        .replace("{fiscal_year_short}", &format!("{:02}", fiscal_year.rem_euclid(100)))
+        .replace("{fiscal_year}", &fiscal_year.to_string())
+        .replace("{column}", &(index + 1).to_string());
+    let label = locale.format(date, &pattern);
 
-                let bottom: Html = dates
-                    .iter()
-                    .enumerate()
-                    .map(|(i, date)| { 
-                        // todo: customize ?                    
-                        let value = format!("Q{}", ((date.month() + 3) / 3));
-                        let key = format!("{}", date.timestamp_nanos());
-                        bottom_calendar(&props, i as f64, value, key)
-                    }).collect();
-
-                (bottom, top)
-            },
-            ViewMode::Year =>  {
-                let dates = props.date_setup.dates.clone().unwrap();
-                let height = props.header_height * 0.5;
-                let top: Html = dates
-                    .iter()
-                    .enumerate()
-                    .filter(|(i, date)| *i == 0 || date.year() != dates.get(i - 1).unwrap().year())
-                    .map(|(i, date)| {
-                        // todo: customize ?
-                        let i = i as f64;
-                        let value = format!("{}", date.format("%Y"));
-                        let x_text = if props.rtl {
-                            (6.0 + i + (date.year() as f64) + 1.0) * props.column_width
-                        } else {
-                            (6.0 + i - (date.year() as f64)) * props.column_width
-                        };
-                        let x_line = props.column_width * i;
-                        top_calendar(&props, x_line, height, x_text, value)
-                    })
-                    .collect();
+    match &props.formatter {
+        Some(formatter) => formatter.emit(CalendarLabelCtx {
+            date: *date,
+            view_mode: props.view_mode.clone(),
+            row,
+            index,
+            label,
+        }),
+        None => label,
+    }
+}
 
-                let bottom: Html = dates
-                    .iter()
-                    .enumerate()
-                    .map(|(i, date)| { 
-                        // todo: customize ?                    
-                        let value = format!("{}", date.format("%Y"));
-                        let key = format!("{}", date.timestamp_nanos());
-                        bottom_calendar(&props, i as f64, value, key)
-                    }).collect();
-
-                (bottom, top)
-            },
+impl MarkerDash {
+    fn dash_array(&self) -> &'static str {
+        match self {
+            MarkerDash::Solid => "none",
+            MarkerDash::Dashed => "6 4",
+            MarkerDash::Dotted => "2 3",
         }
     }
 }
 
 impl TaskTypeInternal {
-    fn get_task_item(&self, task: BarTask) -> Html {
+    /// `on_mouse_down` gets the index of the pressed segment, 0 for a bar without segments.
+    fn get_task_item(
+        &self,
+        task: BarTask,
+        rtl: bool,
+        is_selected: bool,
+        on_mouse_down: Callback<(usize, MouseEvent)>,
+    ) -> Html {
+        let styles = task.styles.clone().unwrap();
+        let bar_color = if is_selected {
+            styles.background_selected_color
+        } else {
+            styles.background_color
+        }
+        .unwrap();
+        let process_color = if is_selected {
+            styles.progress_selected_color
+        } else {
+            styles.progress_color
+        }
+        .unwrap();
+
         match self {
             TaskTypeInternal::Task => {
-                let progress_x = task.progress_x.unwrap() + task.progress_width.unwrap();
+                // progress grows towards the task end, which is on the left side in rtl
+                let progress_x = if rtl {
+                    task.progress_x.unwrap()
+                } else {
+                    task.progress_x.unwrap() + task.progress_width.unwrap()
+                };
                 let task_y = task.y.unwrap();
                 let task_height = task.height.unwrap();
                 let  point = [progress_x - 5.0, task_y + task_height, progress_x + 5.0, task_y + task_height, progress_x, task_y + task_height - 8.66,].iter()
@@ -275,9 +158,12 @@ impl TaskTypeInternal {
                     .collect::<Vec<String>>()
                     .join(",");
 
-                html!{
-                    <g class="bar-wrapper" tabIndex={0}>
-                        <g /* TODO: onMouseDown={onMouseDown}*/>
+                let bar = match &task.segments {
+                    Some(segments) => {
+                        bar_segments(&task, segments, &bar_color, &process_color, &on_mouse_down)
+                    }
+                    None => html! {
+                        <g onmousedown={on_mouse_down.reform(|e| (0, e))}>
                             <rect
                                 x={task.x_1.unwrap().to_string()}
                                 width={(task.x_2.unwrap() - task.x_1.unwrap()).to_string()}
@@ -285,7 +171,7 @@ impl TaskTypeInternal {
                                 height={task.height.unwrap().to_string()}
                                 ry={task.bar_corner_radius.unwrap().to_string()}
                                 rx={task.bar_corner_radius.unwrap().to_string()}
-                                fill={task.styles.clone().unwrap().background_color.unwrap()} // todo! isSelected
+                                style={format!("fill: {}", bar_color)}
                                 class="bar-back-ground"
                             />
                             <rect
@@ -295,9 +181,15 @@ impl TaskTypeInternal {
                                 height={task.height.unwrap().to_string()}
                                 ry={task.bar_corner_radius.unwrap().to_string()}
                                 rx={task.bar_corner_radius.unwrap().to_string()}
-                                fill={task.styles.clone().unwrap().progress_color.unwrap()} // todo! isSelected
+                                style={format!("fill: {}", process_color)}
                             />
                         </g>
+                    },
+                };
+
+                html!{
+                    <g class="bar-wrapper" tabIndex={0}>
+                        {bar}
                         <g class="handle-group">
                             // TODO! if isDateChangeable
                              <g>
@@ -336,21 +228,6 @@ impl TaskTypeInternal {
                 }
             },
             TaskTypeInternal::Project => {
-                // TODO: isSelcted
-                let mut styles = task.styles.clone().unwrap();
-                let bar_color = if true {
-                    styles.background_selected_color
-                } else {
-                    styles.background_color
-                }
-                .unwrap();
-                styles = task.styles.clone().unwrap();
-                let process_color = if true {
-                    styles.progress_selected_color
-                } else {
-                    styles.progress_color
-                }
-                .unwrap();
                 let project_with = task.x_2.unwrap() - task.x_1.unwrap();
 
                 let project_left_triangle = [
@@ -383,7 +260,7 @@ impl TaskTypeInternal {
                 html! {
                     <g tabIndex={0} class="project-wrapper">
                         <rect
-                            fill={bar_color.clone()} // todo! isSelected
+                            style={format!("fill: {}", bar_color)}
                             x={task.x_1.unwrap().to_string()}
                             width={project_with.to_string()}
                             y={task.y.unwrap().to_string()}
@@ -399,10 +276,10 @@ impl TaskTypeInternal {
                             height={task.height.unwrap().to_string()}
                             ry={task.bar_corner_radius.unwrap().to_string()}
                             rx={task.bar_corner_radius.unwrap().to_string()}
-                            fill={process_color} // todo! isSelected
+                            style={format!("fill: {}", process_color)}
                         />
                         <rect
-                            fill={bar_color.clone()}
+                            style={format!("fill: {}", bar_color)}
                             x={task.x_1.unwrap().to_string()}
                             width={project_with.to_string()}
                             y={task.y.unwrap().to_string()}
@@ -414,24 +291,25 @@ impl TaskTypeInternal {
                         <polygon
                             class="project-top"
                             points={project_left_triangle}
-                            fill={bar_color.clone()} // todo! isSelected
+                            style={format!("fill: {}", bar_color)}
                         />
                         <polygon
                             class="project-top"
                             points={project_right_triangle}
-                            fill={bar_color.clone()} // todo! isSelected
+                            style={format!("fill: {}", bar_color)}
                         />
                     </g>
                 };
                 node
             }
             TaskTypeInternal::Milestone => {
-                let transform = format!("rotate(45 {} {})", task.x_1.unwrap() + task.height.unwrap() * 0.356, task.y.unwrap() + task.height.unwrap() * 0.85);
+                let x = if rtl { task.x_2.unwrap() - task.height.unwrap() } else { task.x_1.unwrap() };
+                let transform = format!("rotate(45 {} {})", x + task.height.unwrap() * 0.356, task.y.unwrap() + task.height.unwrap() * 0.85);
                 html!{
                     <g tabIndex={0} class="milestone-wrapper">
                         <rect
-                            fill={task.styles.clone().unwrap().background_color.unwrap()} // todo! isSelected
-                            x={task.x_1.unwrap().to_string()}
+                            style={format!("fill: {}", bar_color)}
+                            x={x.to_string()}
                             width={task.height.unwrap().to_string()}
                             y={task.y.unwrap().to_string()}
                             height={task.height.unwrap().to_string()}
@@ -455,16 +333,38 @@ pub struct SvgView {
     calendar_props: CalendarProps,
     bar_props: TaskGanttContentProps,
     gantt_height: f64,
-    scroll_y: f64,
     scroll_x: f64,
+    on_zoom: Option<yew::Callback<ZoomEvent>>,
+    // distance of the two fingers of a pinch
+    pinch: Rc<Cell<Option<f64>>>,
+    visible_rows: Range<usize>,
+    visible_columns: Range<usize>,
+    table_body_ref: yew::NodeRef,
+    on_viewport: Callback<Viewport>,
+    on_bar_event: Callback<BarEvent>,
+    on_group_toggle: Callback<String>,
+    // task, segment and pointer x where a segment drag started
+    drag: Rc<Cell<Option<(usize, usize, f64)>>>,
+    // task whose bar is under the pointer, it has a tooltip
+    hovered: Option<usize>,
+    // window scroll and resize move the viewport of a chart without own scrollbars, segment
+    // drags go on outside of the chart
+    listeners: Rc<Vec<EventListener>>,
 
     vertical_gantt_container_ref: yew::NodeRef,
     horizontal_container_ref: yew::NodeRef,
     gantt_svg_ref: yew::NodeRef,
 }
 
+pub enum SvgMsg {
+    /// Forwarded to `on_bar_event`, the window listeners outlive the first callback prop.
+    Bar(BarEvent),
+    /// The pointer entered the bar of the task with the index, or left it.
+    Hover(Option<usize>),
+}
+
 impl Component for SvgView {
-    type Message = ();
+    type Message = SvgMsg;
     type Properties = SvgProps;
 
     fn create(ctx: &Context<Self>) -> Self {
@@ -473,211 +373,400 @@ impl Component for SvgView {
             calendar_props: ctx.props().calendar_props.clone(), 
             bar_props: ctx.props().bar_props.clone(), 
             gantt_height:ctx.props().gantt_height, 
-            scroll_y: ctx.props().scroll_y, 
             scroll_x: ctx.props().scroll_x, 
+            on_zoom: ctx.props().on_zoom.clone(),
+            pinch: Rc::default(),
+            visible_rows: ctx.props().visible_rows.clone(),
+            visible_columns: ctx.props().visible_columns.clone(),
+            table_body_ref: ctx.props().table_body_ref.clone(),
+            on_viewport: ctx.props().on_viewport.clone(),
+            on_bar_event: ctx.props().on_bar_event.clone(),
+            on_group_toggle: ctx.props().on_group_toggle.clone(),
+            drag: Rc::default(),
+            hovered: None,
+            listeners: Rc::default(),
 
-            vertical_gantt_container_ref: yew::NodeRef::default(), 
+            vertical_gantt_container_ref: ctx.props().container_ref.clone(), 
             horizontal_container_ref: yew::NodeRef::default(), 
             gantt_svg_ref: yew::NodeRef::default()
         }
     }
 
-    fn view(&self, _ctx: &Context<Self>) -> Html {
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            SvgMsg::Bar(event) => {
+                self.on_bar_event.emit(event);
+                false
+            }
+            SvgMsg::Hover(task) => std::mem::replace(&mut self.hovered, task) != task,
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        let props = ctx.props();
+        self.grid_props = props.grid_props.clone();
+        self.calendar_props = props.calendar_props.clone();
+        self.bar_props = props.bar_props.clone();
+        self.gantt_height = props.gantt_height;
+        self.scroll_x = props.scroll_x;
+        self.on_zoom = props.on_zoom.clone();
+        self.vertical_gantt_container_ref = props.container_ref.clone();
+        self.visible_rows = props.visible_rows.clone();
+        self.visible_columns = props.visible_columns.clone();
+        self.table_body_ref = props.table_body_ref.clone();
+        self.on_viewport = props.on_viewport.clone();
+        self.on_bar_event = props.on_bar_event.clone();
+        self.on_group_toggle = props.on_group_toggle.clone();
+        true
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if first_render {
+            let report = self.report_viewport();
+            report();
+            if let Some(window) = web_sys::window() {
+                let drag = |done: bool| {
+                    let drag = self.drag.clone();
+                    let on_bar_event = ctx.link().callback(SvgMsg::Bar);
+                    move |event: &web_sys::Event| {
+                        let Some(event) = event.dyn_ref::<web_sys::MouseEvent>() else {
+                            return;
+                        };
+                        let Some((task, segment, x)) = drag.get() else {
+                            return;
+                        };
+                        if done {
+                            drag.set(None);
+                        }
+                        let dx = event.client_x() as f64 - x;
+                        on_bar_event.emit(BarEvent::SegmentDrag { task, segment, dx, done });
+                    }
+                };
+                self.listeners = Rc::new(vec![
+                    EventListener::new(&window, "scroll", {
+                        let report = report.clone();
+                        move |_| report()
+                    }),
+                    EventListener::new(&window, "resize", move |_| report()),
+                    EventListener::new(&window, "mousemove", drag(false)),
+                    EventListener::new(&window, "mouseup", drag(true)),
+                ]);
+            }
+        }
+        if self.scroll_x < 0.0 {
+            return;
+        }
+        if let Some(container) = self.vertical_gantt_container_ref.cast::<web_sys::Element>() {
+            set_scroll_position(&container, self.bar_props.rtl, self.scroll_x);
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let on_scroll = {
+            let report = self.report_viewport();
+            let container = self.vertical_gantt_container_ref.clone();
+            let table_body = self.table_body_ref.clone();
+            Callback::from(move |_: Event| {
+                if let (Some(container), Some(table_body)) = (
+                    container.cast::<web_sys::Element>(),
+                    table_body.cast::<web_sys::Element>(),
+                ) {
+                    if table_body.scroll_top() != container.scroll_top() {
+                        table_body.set_scroll_top(container.scroll_top());
+                    }
+                }
+                report();
+            })
+        };
         let calendar_props = self.calendar_props.clone();
-        let cal_values = calendar_props
-            .date_setup
-            .view_mode
-            .unwrap()
-            .get_values(self.calendar_props.clone());
-        let mut y = 0.0;
+        let cal_values = calendar_props.scale.header(&calendar_props);
         let grid_props = self.grid_props.clone();
+        let rows = self.visible_rows.clone();
+        let columns = self.visible_columns.clone();
+        let y = grid_props.row_height * (grid_props.rows.len() as f64);
+        let row_key = |row: &Row| match row {
+            Row::Group(group) => format!("Group{}", group.key),
+            Row::Task(i) => format!("Row{}", grid_props.tasks[*i].id.clone().unwrap()),
+            Row::Lane(lane) => format!("Lane{}-{}", lane.key, lane.lane),
+        };
         let grid_rows: Html = grid_props
-            .tasks
+            .rows
             .iter()
-            .map(|task| {
-                let node = html! {
+            .enumerate()
+            .skip(rows.start)
+            .take(rows.len())
+            .map(|(i, row)| {
+                let (class, onclick) = match row {
+                    Row::Group(group) => {
+                        let key = group.key.clone();
+                        let onclick = self.on_group_toggle.reform(move |_| key.clone());
+                        ("grid-row grid-group-row", Some(onclick))
+                    }
+                    Row::Task(_) | Row::Lane(_) => ("grid-row", None),
+                };
+                html! {
                     <rect
-                        key={format!("Row{}", task.id.clone().unwrap())}
+                        key={row_key(row)}
                         x="0"
-                        y={y.to_string()}
+                        y={(grid_props.row_height * (i as f64)).to_string()}
                         width={grid_props.svg_width.to_string()}
                         height={grid_props.row_height.to_string()}
-                        class="grid-row"
+                        class={class}
+                        onclick={onclick}
                   />
-                };
-                y += grid_props.row_height;
-                node
+                }
             })
             .collect();
 
-        y = 0.0;
         let row_lines: Html = grid_props
-            .tasks
+            .rows
             .iter()
-            .map(|task| {
-                let node = html! {
+            .enumerate()
+            .skip(rows.start)
+            .take(rows.len())
+            .map(|(i, row)| {
+                let line_y = grid_props.row_height * ((i + 1) as f64);
+                html! {
                     <line
-                        key={format!("Row{}", task.id.clone().unwrap())}
+                        key={row_key(row)}
                         x="0"
-                        y1={(y + grid_props.row_height).to_string()}
+                        y1={line_y.to_string()}
                         x2={grid_props.svg_width.to_string()}
-                        y2={(y + grid_props.row_height).to_string()}
+                        y2={line_y.to_string()}
                         class="grid-row-line"
                   />
-                };
-                y += grid_props.row_height;
-                node
+                }
             })
             .collect();
 
         let dates = grid_props.dates;
-        let now = Utc::now().naive_utc();
-        let mut today = html! {};
-        let mut tick_x = 0.0;
+        let now = time_zone::now();
         let ticks: Html = dates.iter().enumerate()
+            .skip(columns.start)
+            .take(columns.len())
             .map(|(i, date)|{
-                let node = html! {
+                let tick_x = grid_props.column_width * (i as f64);
+                let line_x = if grid_props.rtl { grid_props.svg_width - tick_x } else { tick_x };
+                html! {
                     <line
-                        key={date.timestamp_nanos().to_string()}
-                        x1={tick_x.to_string()}
+                        key={date.and_utc().timestamp_nanos_opt().unwrap_or_default().to_string()}
+                        x1={line_x.to_string()}
                         y1={0}
-                        x2={tick_x.to_string()}
+                        x2={line_x.to_string()}
                         y2={y.to_string()}
                         class="grid-tick"
                     />
+                }
+            }).collect();
+
+        let today_index = dates.iter().enumerate().position(|(i, date)| {
+            let last = i + 1 == dates.len();
+            (!last && *date < now && dates[i + 1] >= now)
+                // if current date is last
+                || (i != 0 && last && *date < now && *date + (*date - dates[i - 1]) >= now)
+        });
+        let today = match today_index {
+            Some(i) => {
+                let tick_x = grid_props.column_width * (i as f64);
+                let today_x = if grid_props.rtl {
+                    grid_props.svg_width - tick_x - grid_props.column_width
+                } else {
+                    tick_x
                 };
-                
-                
-                if (i + 1 != dates.len() && date.timestamp_micros() < now.timestamp_micros() && dates[i + 1].timestamp_micros() >= now.timestamp_micros()) 
-                    // if current date is last
-                    || (i != 0 && i + 1 == dates.len() && date.timestamp_micros() < now.timestamp_micros() &&
-                    NaiveDateTime::from_timestamp_micros(date.timestamp_micros() - dates[i - 1].timestamp_micros()).unwrap().timestamp_micros() >= now.timestamp_micros())
-                {
-                    today = html! {
+                html! {
                     <rect
-                        x={tick_x.to_string()}
+                        x={today_x.to_string()}
                         y={0}
                         width={grid_props.column_width.to_string()}
                         height={y.to_string()}
-                        fill={grid_props.today_color.clone()}
+                        style={format!("fill: {}", grid_props.today_color)}
                     />
-                    };
                 }
+            }
+            None => html! {},
+        };
 
-                tick_x += grid_props.column_width;
-                node
-            }).collect();
+        let markers: Html = grid_props
+            .markers
+            .iter()
+            .map(|marker| grid_marker(marker, y))
+            .collect();
+        let marker_labels: Html = grid_props
+            .markers
+            .iter()
+            .map(|marker| calendar_marker(marker, self.calendar_props.header_height, grid_props.rtl))
+            .collect();
 
         let tasks = self.bar_props.tasks.clone();
+        // tasks of collapsed groups have no row of their own and no arrows
+        let mut shown = vec![false; tasks.len()];
+        for row in &self.grid_props.rows {
+            match row {
+                Row::Group(_) => {}
+                Row::Task(i) => shown[*i] = true,
+                Row::Lane(lane) => lane.tasks.iter().for_each(|i| shown[*i] = true),
+            }
+        }
+        // arrows crossing the visible rows too, their ends come from the full layout, which leaves
+        // out the ones to tasks without a row
         let arrow_tasks: Html = tasks
             .iter()
-            .map(|task| {
+            .zip(&shown)
+            .filter(|(_, shown)| **shown)
+            .map(|(task, _)| {
                 let nodes: Html = task
                     .bar_children
                     .clone()
                     .unwrap()
                     .iter()
-                    .map(|children| {
-                        // todo: do we need rtl
-                        let index_compare = if task.index.unwrap() > children.index.unwrap() {
-                            -1.0
-                        } else {
-                            1.0
-                        };
-                        let task_to_end_position =
-                            children.y.unwrap() + self.bar_props.task_height / 2.0;
-                        let task_from_end_position =
-                            task.x_2.unwrap() + self.bar_props.arrow_indent * 2.0;
-                        let task_from_horizontal_offset_value =
-                            if task_from_end_position < children.x_1.unwrap() {
-                                "".to_owned()
-                            } else {
-                                format!("H {}", children.x_1.unwrap() - self.bar_props.arrow_indent)
-                            };
-                        let task_to_horizontal_offset_value = if task_from_end_position
-                            > children.x_1.unwrap()
-                        {
-                            self.bar_props.arrow_indent
-                        } else {
-                            children.x_1.unwrap() - task.x_2.unwrap() - self.bar_props.arrow_indent
-                        };
-
-                        let path = format!(
-                            "M {} {} h {} v {} {} V {} h {}",
-                            task.x_2.unwrap(),
-                            task.y.unwrap() + self.bar_props.task_height / 2.0,
-                            self.bar_props.arrow_indent,
-                            (index_compare * self.bar_props.row_height) / 2.0,
-                            task_from_horizontal_offset_value,
-                            task_to_end_position,
-                            task_to_horizontal_offset_value
-                        );
-
-                        let triangle_points = format!(
-                            "{},{} {},{} {},{}",
-                            children.x_1.unwrap(),
-                            task_to_end_position,
-                            children.x_1.unwrap() - 5.0,
-                            task_to_end_position - 5.0,
-                            children.x_1.unwrap() - 5.0,
-                            task_to_end_position + 5.0
-                        );
-
-                        html! {
-                            <g class="arrow">
-                                <path strok-width="1.5" d={path} fill="none" />
-                                <polygon points={triangle_points} />
-                            </g>
-                        }
+                    .filter(|children| {
+                        let from = task.index.unwrap_or_default() as usize;
+                        let to = children.index.unwrap_or_default() as usize;
+                        from.min(to) < rows.end && from.max(to) >= rows.start
                     })
+                    .map(|children| arrow(&self.bar_props, task, children))
                     .collect();
                 nodes
             })
             .collect();
 
-        let bar_tasks: Html = tasks
+        let rtl = self.bar_props.rtl;
+        let container = self.vertical_gantt_container_ref.clone();
+        // pointer position on the chart and in its visible part
+        let pointer = move |client_x: f64| {
+            let element = container.cast::<web_sys::Element>()?;
+            let offset = client_x - element.get_bounding_client_rect().left();
+            Some((scroll_position(&element, rtl) + offset, offset))
+        };
+
+        let bar = |i: usize| {
+            let task = &tasks[i];
+            // alt+click splits a bar, segments of a split one are dragged on their own
+            let on_mouse_down = {
+                let drag = self.drag.clone();
+                let pointer = pointer.clone();
+                let on_bar_event = ctx.link().callback(SvgMsg::Bar);
+                let split = task.segments.is_some();
+                Callback::from(move |(segment, e): (usize, MouseEvent)| {
+                    if e.button() != 0 {
+                        return;
+                    }
+                    if e.alt_key() {
+                        if let Some((x, _)) = pointer(e.client_x() as f64) {
+                            on_bar_event.emit(BarEvent::Split { task: i, x });
+                        }
+                    } else if split {
+                        e.prevent_default();
+                        drag.set(Some((i, segment, e.client_x() as f64)));
+                    }
+                })
+            };
+            let dimmed = task.dimmed.unwrap_or(false).then_some("bar-dimmed");
+            let link = ctx.link();
+            let on_select = link.callback(move |_| SvgMsg::Bar(BarEvent::Select { task: i }));
+            html! {
+                <g
+                    class={dimmed}
+                    onmousedown={on_select}
+                    onmouseenter={link.callback(move |_| SvgMsg::Hover(Some(i)))}
+                    onmouseleave={link.callback(|_| SvgMsg::Hover(None))}
+                    /*onKeyDown= TODO!! */
+                >
+                    {task.type_internal.clone().unwrap().get_task_item(
+                        task.clone(),
+                        self.bar_props.rtl,
+                        self.bar_props.selected_task.task.id == task.task.id,
+                        on_mouse_down,
+                    )}
+                    {bar_label(&self.bar_props, task)}
+                </g>
+            }
+        };
+        let bar_tasks: Html = self
+            .grid_props
+            .rows
             .iter()
-            .map(|task| {
-                let width = task.x_2.unwrap() - task.x_1.unwrap();
-                let _has_child = !task.bar_children.clone().unwrap().is_empty();
-                // todo! isTextInside
-                let x = if true {
-                    task.x_1.unwrap() + width * 0.5
-                } else {
-                    // todo rtl + ref
-                    task.x_1.unwrap()
-                        + width
-                        + self.bar_props.arrow_indent
-                        + self.bar_props.arrow_indent * 0.2
+            .enumerate()
+            .skip(rows.start)
+            .take(rows.len())
+            .map(|(row, it)| match it {
+                Row::Group(group) => group_bar(&self.bar_props, group, row),
+                Row::Task(i) => bar(*i),
+                Row::Lane(lane) => lane.tasks.iter().map(|i| bar(*i)).collect(),
+            })
+            .collect();
+        let on_wheel = self.on_zoom.clone().map(|on_zoom| {
+            let pointer = pointer.clone();
+            Callback::from(move |e: WheelEvent| {
+                if !e.ctrl_key() {
+                    return;
+                }
+                e.prevent_default();
+                // line and page deltas are rare, treat them as 16px per line
+                let delta = match e.delta_mode() {
+                    WheelEvent::DOM_DELTA_PIXEL => e.delta_y(),
+                    _ => e.delta_y() * 16.0,
                 };
-
-                html! {
-                    <g /*onKeyDown= TODO!! */ >
-                        {task.type_internal.clone().unwrap().get_task_item(task.clone())}
-                        <text
-                            x={x.to_string()}
-                            y={(task.y.unwrap() + self.bar_props.task_height * 0.5).to_string()}
-                            // class={ if isTextInside
-                            //     ? style.barLabel
-                            //     : style.barLabel && style.barLabelOutside
-                            // }
-                            // ref={textRef}
-                        >
-                            {task.task.name.clone()}
-                        </text>
-                    </g>
+                if let Some((x, offset)) = pointer(e.client_x() as f64) {
+                    on_zoom.emit(ZoomEvent { factor: (-delta * 0.002).exp(), x, offset });
                 }
             })
-            .collect();
+        });
+        let on_touch_move = self.on_zoom.clone().map(|on_zoom| {
+            let pinch = self.pinch.clone();
+            Callback::from(move |e: TouchEvent| {
+                let touches = e.touches();
+                let (Some(a), Some(b)) = (touches.get(0), touches.get(1)) else {
+                    pinch.set(None);
+                    return;
+                };
+                // touch listeners are passive, `touch-action` keeps the page from zooming
+                let (dx, dy) = (a.client_x() - b.client_x(), a.client_y() - b.client_y());
+                let distance = ((dx * dx + dy * dy) as f64).sqrt();
+                let center = (a.client_x() + b.client_x()) as f64 * 0.5;
+                if let (Some(last), Some((x, offset))) = (pinch.get(), pointer(center)) {
+                    if last > 0.0 {
+                        on_zoom.emit(ZoomEvent { factor: distance / last, x, offset });
+                    }
+                }
+                pinch.set(Some(distance));
+            })
+        });
+        let on_touch_end = {
+            let pinch = self.pinch.clone();
+            Callback::from(move |_: TouchEvent| pinch.set(None))
+        };
+
+        // not while a segment is dragged
+        let tooltip = self
+            .hovered
+            .filter(|_| self.drag.get().is_none())
+            .and_then(|i| tasks.get(i))
+            .filter(|task| task.y.is_some())
+            .map(|task| tooltip(&self.bar_props, task, self.calendar_props.header_height));
 
         html! {
             <div
                 class="gantt-vertical-container" // todo: add scss!!
-                ref={self.vertical_gantt_container_ref.clone()} // todo: scroll_x render
-                dir="ltr"
+                ref={self.vertical_gantt_container_ref.clone()}
+                dir={if self.bar_props.rtl { "rtl" } else { "ltr" }}
+                // a chart with a height scrolls vertically itself, below its sticky calendar
+                style={
+                    if self.gantt_height > 0.0 {
+                        let height = self.calendar_props.header_height + self.gantt_height;
+                        format!("height: {}px; overflow-y: auto", height)
+                    } else {
+                        String::new()
+                    }
+                }
+                onscroll={on_scroll}
+                onwheel={on_wheel}
+                ontouchmove={on_touch_move}
+                ontouchend={on_touch_end}
                 >
                 <svg
                     xmlns="http://www.w3.org/2000/svg"
+                    class="gantt-calendar"
                     width={self.grid_props.svg_width.to_string()}
                     height={self.calendar_props.header_height.to_string()}
                     font-family={self.bar_props.font_family.clone()}
@@ -692,21 +781,19 @@ impl Component for SvgView {
                         />
                         {cal_values.0} 
                         {cal_values.1}
+                        <g class="calendar-markers">{marker_labels}</g>
                     </g>
                 </svg>
 
                 <div
                     ref={self.horizontal_container_ref.clone()}
                     class="horizontal-container"
-                    style={
-                        if self.gantt_height > 0.0 { format!("height:{}; width:{}", self.gantt_height, self.grid_props.svg_width) }
-                        else { format!("width:{}", self.grid_props.svg_width) }
-                    }
+                    style={format!("width: {}px", self.grid_props.svg_width)}
                     >
                     <svg
                         xmlns="http://www.w3.org/2000/svg"
                         width={self.grid_props.svg_width.to_string()}
-                        height={(self.bar_props.row_height * (self.bar_props.tasks.len() as f64)).to_string()}
+                        height={y.to_string()}
                         font-family={self.bar_props.font_family.to_string()}
                         ref={self.gantt_svg_ref.clone()}
                     >
@@ -716,14 +803,16 @@ impl Component for SvgView {
                                 <g class="row-lines">{row_lines}</g>
                                 <g class="ticks">{ticks}</g>
                                 <g class="today">{today}</g>
+                                <g class="markers">{markers}</g>
                             </g>
                         </g>
                         <g class="content">
-                            <g class="arrows" fill={self.bar_props.arrow_color.clone()} stroke={self.bar_props.arrow_color.clone()}>{arrow_tasks}</g>
+                            <g class="arrows" style={format!("fill: {0}; stroke: {0}", self.bar_props.arrow_color)}>{arrow_tasks}</g>
                             <g class="bar" font-family={self.bar_props.font_family.clone()} font-size={self.bar_props.font_size.clone()}>{bar_tasks}</g>
                         </g>
                     </svg>
                 </div>
+                {tooltip}
             </div>
         }
     }
@@ -734,7 +823,7 @@ fn bottom_calendar(props: &CalendarProps, i: f64,  value: String, key: String )
         <text
             key={key}
             y={(props.header_height * 0.8).to_string()}
-            x={(props.column_width * i + props.column_width * 0.5).to_string()}
+            x={mirror(props, props.column_width * i + props.column_width * 0.5).to_string()}
             class="calendar-bottom-text"
         >
             {value}
@@ -743,6 +832,8 @@ fn bottom_calendar(props: &CalendarProps, i: f64,  value: String, key: String )
 }
 
 fn top_calendar(props: &CalendarProps, x_line: f64, height: f64, x_text: f64, value: String) -> yew::virtual_dom::VNode {
+    let x_line = mirror(props, x_line);
+    let x_text = mirror(props, x_text);
     html! {
         <g class="calendar-top">
             <line
@@ -764,3 +855,407 @@ fn top_calendar(props: &CalendarProps, x_line: f64, height: f64, x_text: f64, va
         </g>
     }
 }
+
+/// Tooltip next to the bar, towards the end of the line, below the bar. The calendar above the
+/// rows is `header_height` high.
+fn tooltip(props: &TaskGanttContentProps, task: &BarTask, header_height: f64) -> Html {
+    let ctx = TooltipCtx {
+        task: task.task.clone(),
+        font_family: props.font_family.clone(),
+        font_size: props.font_size.clone(),
+    };
+    let content = match &props.tooltip_content {
+        Some(tooltip_content) => tooltip_content.emit(ctx),
+        None => {
+            let progress = task.task.progress.unwrap_or_default();
+            html! {
+                <div class="gantt-tooltip-default">
+                    <b>{task.task.name.clone().unwrap_or_default()}</b>
+                    <p>
+                        {task.task.start.clone().unwrap_or_default()}
+                        {" – "}
+                        {task.task.end.clone().unwrap_or_default()}
+                    </p>
+                    if progress > 0.0 {
+                        <p>{format!("Progress: {}%", progress.round())}</p>
+                    }
+                </div>
+            }
+        }
+    };
+    let (x_1, x_2) = (task.x_1.unwrap_or_default(), task.x_2.unwrap_or_default());
+    let left = if props.rtl {
+        format!("left: {}px; transform: translateX(-100%)", x_1 - 8.0)
+    } else {
+        format!("left: {}px", x_2 + 8.0)
+    };
+    let top = header_height + task.y.unwrap_or_default() + task.height.unwrap_or_default() + 4.0;
+    html! {
+        <div
+            class="gantt-tooltip"
+            style={format!(
+                "{}; top: {}px; font-family: {}; font-size: {}",
+                left, top, props.font_family, props.font_size
+            )}
+        >
+            {content}
+        </div>
+    }
+}
+
+/// Summary bar of a group header row, from the first start to the last end of its tasks.
+fn group_bar(props: &TaskGanttContentProps, group: &GroupRow, row: usize) -> Html {
+    if group.x_2 <= group.x_1 {
+        return html! {};
+    }
+    let height = props.task_height * 0.5;
+    let y = props.row_height * (row as f64) + (props.row_height - height) * 0.5;
+    html! {
+        <rect
+            key={format!("Group{}", group.key)}
+            x={group.x_1.to_string()}
+            width={(group.x_2 - group.x_1).to_string()}
+            y={y.to_string()}
+            height={height.to_string()}
+            class="gantt-group-bar"
+        />
+    }
+}
+
+/// Work segments of a split task joined by dotted connectors over the gaps. Progress fills the
+/// segments one after another.
+fn bar_segments(
+    task: &BarTask,
+    segments: &[BarSegment],
+    bar_color: &str,
+    progress_color: &str,
+    on_mouse_down: &Callback<(usize, MouseEvent)>,
+) -> Html {
+    let y = task.y.unwrap_or_default();
+    let height = task.height.unwrap_or_default();
+    let radius = task.bar_corner_radius.unwrap_or_default().to_string();
+    let mut ordered: Vec<&BarSegment> = segments.iter().collect();
+    ordered.sort_by(|a, b| a.x_1.total_cmp(&b.x_1));
+    let connectors: Html = ordered
+        .windows(2)
+        .filter(|it| it[1].x_1 > it[0].x_2)
+        .map(|it| {
+            html! {
+                <line
+                    x1={it[0].x_2.to_string()}
+                    y1={(y + height * 0.5).to_string()}
+                    x2={it[1].x_1.to_string()}
+                    y2={(y + height * 0.5).to_string()}
+                    style={format!("stroke: {}", bar_color)}
+                    class="bar-segment-connector"
+                />
+            }
+        })
+        .collect();
+    let rects: Html = segments
+        .iter()
+        .enumerate()
+        .map(|(i, segment)| {
+            html! {
+                <g class="bar-segment" onmousedown={on_mouse_down.reform(move |e| (i, e))}>
+                    <rect
+                        x={segment.x_1.to_string()}
+                        width={(segment.x_2 - segment.x_1).to_string()}
+                        y={y.to_string()}
+                        height={height.to_string()}
+                        ry={radius.clone()}
+                        rx={radius.clone()}
+                        style={format!("fill: {}", bar_color)}
+                        class="bar-back-ground"
+                    />
+                    <rect
+                        x={segment.progress_x.to_string()}
+                        width={segment.progress_width.to_string()}
+                        y={y.to_string()}
+                        height={height.to_string()}
+                        ry={radius.clone()}
+                        rx={radius.clone()}
+                        style={format!("fill: {}", progress_color)}
+                    />
+                </g>
+            }
+        })
+        .collect();
+
+    html! {
+        <g>
+            {connectors}
+            {rects}
+        </g>
+    }
+}
+
+/// Label is centred inside the bar when it fits, otherwise it is placed after the bar
+/// (before it in rtl) and truncated at the next bar in the row or at the chart edge.
+fn bar_label(props: &TaskGanttContentProps, task: &BarTask) -> Html {
+    let name = task.task.name.clone().unwrap_or_default();
+    let (x_1, x_2) = match task.type_internal {
+        Some(TaskTypeInternal::Milestone) if props.rtl => {
+            (task.x_2.unwrap() - task.height.unwrap(), task.x_2.unwrap())
+        }
+        Some(TaskTypeInternal::Milestone) => {
+            (task.x_1.unwrap(), task.x_1.unwrap() + task.height.unwrap())
+        }
+        _ => (task.x_1.unwrap(), task.x_2.unwrap()),
+    };
+    let y = (task.y.unwrap() + props.task_height * 0.5).to_string();
+    let label_width = text::text_width(&name, &props.font_size, &props.font_family);
+    let is_text_inside =
+        task.type_internal != Some(TaskTypeInternal::Milestone) && label_width < x_2 - x_1;
+
+    if is_text_inside {
+        return html! {
+            <text x={(x_1 + (x_2 - x_1) * 0.5).to_string()} y={y} class="bar-label">
+                {name}
+            </text>
+        };
+    }
+
+    let offset = props.arrow_indent + props.arrow_indent * 0.2;
+    let same_row = props
+        .tasks
+        .iter()
+        .filter(|it| it.y == task.y && it.task.id != task.task.id);
+    // text-anchor follows the `dir` of the container, so "start" is the right edge in rtl
+    let (x, available) = if props.rtl {
+        let x = x_1 - offset;
+        let limit = same_row
+            .filter_map(|it| it.x_2)
+            .filter(|it| *it <= x_1)
+            .fold(0.0, f64::max);
+        (x, x - limit - props.arrow_indent * 0.2)
+    } else {
+        let x = x_2 + offset;
+        let limit = same_row
+            .filter_map(|it| it.x_1)
+            .filter(|it| *it >= x_2)
+            .fold(props.svg_width, f64::min);
+        (x, limit - x - props.arrow_indent * 0.2)
+    };
+    let value = text::truncate(&name, available, &props.font_size, &props.font_family);
+
+    html! {
+        <text x={x.to_string()} y={y} class="bar-label bar-label-outside">
+            <title>{name}</title>
+            {value}
+        </text>
+    }
+}
+
+/// Scroll position from the left edge of the chart. RTL containers count `scrollLeft` from
+/// their right edge, down to negative values.
+fn scroll_position(element: &web_sys::Element, rtl: bool) -> f64 {
+    let scroll_left = element.scroll_left() as f64;
+    if rtl {
+        scroll_left + (element.scroll_width() - element.client_width()) as f64
+    } else {
+        scroll_left
+    }
+}
+
+fn set_scroll_position(element: &web_sys::Element, rtl: bool, x: f64) {
+    let x = if rtl {
+        x - (element.scroll_width() - element.client_width()) as f64
+    } else {
+        x
+    };
+    element.set_scroll_left(x.round() as i32);
+}
+
+impl SvgView {
+    /// Emits the visible part of the chart body, in chart coordinates.
+    fn report_viewport(&self) -> Rc<dyn Fn()> {
+        let container = self.vertical_gantt_container_ref.clone();
+        let body = self.horizontal_container_ref.clone();
+        let header_height = self.calendar_props.header_height;
+        let on_viewport = self.on_viewport.clone();
+        Rc::new(move || {
+            let (Some(container), Some(body), Some(window)) = (
+                container.cast::<web_sys::Element>(),
+                body.cast::<web_sys::Element>(),
+                web_sys::window(),
+            ) else {
+                return;
+            };
+            let inner = |value: Result<wasm_bindgen::JsValue, _>| {
+                value.ok().and_then(|it| it.as_f64()).unwrap_or_default()
+            };
+            let window_width = inner(window.inner_width());
+            let window_height = inner(window.inner_height());
+            let clip = container.get_bounding_client_rect();
+            let body = body.get_bounding_client_rect();
+            // the calendar stays on top of a scrolling chart
+            let top = (clip.top() + header_height).max(body.top()).max(0.0);
+            let bottom = clip.bottom().min(window_height);
+            let left = clip.left().max(0.0);
+            let right = clip.right().min(window_width);
+            on_viewport.emit(Viewport {
+                x: left - body.left(),
+                y: top - body.top(),
+                width: (right - left).max(0.0),
+                height: (bottom - top).max(0.0),
+            });
+        })
+    }
+}
+
+/// Calendar is laid out left to right, rtl flips it around the header width.
+fn mirror(props: &CalendarProps, x: f64) -> f64 {
+    if props.rtl {
+        props.column_width * (props.date_setup.dates.clone().unwrap_or_default().len() as f64) - x
+    } else {
+        x
+    }
+}
+
+/// Dependency arrow from the end of `task` to the start of `children`.
+fn arrow(props: &TaskGanttContentProps, task: &BarTask, children: &BarTask) -> Html {
+    let index_compare = if task.index.unwrap() > children.index.unwrap() {
+        -1.0
+    } else {
+        1.0
+    };
+    let task_to_end_position = children.y.unwrap() + props.task_height / 2.0;
+
+    let (path, triangle_points) = if props.rtl {
+        let task_from_end_position = task.x_1.unwrap() - props.arrow_indent * 2.0;
+        let task_from_horizontal_offset_value = if task_from_end_position > children.x_2.unwrap() {
+            "".to_owned()
+        } else {
+            format!("H {}", children.x_2.unwrap() + props.arrow_indent)
+        };
+        let task_to_horizontal_offset_value = if task_from_end_position < children.x_2.unwrap() {
+            -props.arrow_indent
+        } else {
+            children.x_2.unwrap() - task.x_1.unwrap() + props.arrow_indent
+        };
+
+        let path = format!(
+            "M {} {} h {} v {} {} V {} h {}",
+            task.x_1.unwrap(),
+            task.y.unwrap() + props.task_height / 2.0,
+            -props.arrow_indent,
+            (index_compare * props.row_height) / 2.0,
+            task_from_horizontal_offset_value,
+            task_to_end_position,
+            task_to_horizontal_offset_value
+        );
+        let triangle_points = format!(
+            "{},{} {},{} {},{}",
+            children.x_2.unwrap(),
+            task_to_end_position,
+            children.x_2.unwrap() + 5.0,
+            task_to_end_position + 5.0,
+            children.x_2.unwrap() + 5.0,
+            task_to_end_position - 5.0
+        );
+        (path, triangle_points)
+    } else {
+        let task_from_end_position = task.x_2.unwrap() + props.arrow_indent * 2.0;
+        let task_from_horizontal_offset_value = if task_from_end_position < children.x_1.unwrap() {
+            "".to_owned()
+        } else {
+            format!("H {}", children.x_1.unwrap() - props.arrow_indent)
+        };
+        let task_to_horizontal_offset_value = if task_from_end_position > children.x_1.unwrap() {
+            props.arrow_indent
+        } else {
+            children.x_1.unwrap() - task.x_2.unwrap() - props.arrow_indent
+        };
+
+        let path = format!(
+            "M {} {} h {} v {} {} V {} h {}",
+            task.x_2.unwrap(),
+            task.y.unwrap() + props.task_height / 2.0,
+            props.arrow_indent,
+            (index_compare * props.row_height) / 2.0,
+            task_from_horizontal_offset_value,
+            task_to_end_position,
+            task_to_horizontal_offset_value
+        );
+        let triangle_points = format!(
+            "{},{} {},{} {},{}",
+            children.x_1.unwrap(),
+            task_to_end_position,
+            children.x_1.unwrap() - 5.0,
+            task_to_end_position - 5.0,
+            children.x_1.unwrap() - 5.0,
+            task_to_end_position + 5.0
+        );
+        (path, triangle_points)
+    };
+
+    html! {
+        <g class="arrow">
+            <path strok-width="1.5" d={path} fill="none" />
+            <polygon points={triangle_points} />
+        </g>
+    }
+}
+
+fn grid_marker(marker: &GridMarker, height: f64) -> Html {
+    if marker.is_range {
+        html! {
+            <rect
+                x={marker.x_1.to_string()}
+                y={0}
+                width={(marker.x_2 - marker.x_1).to_string()}
+                height={height.to_string()}
+                style={format!("fill: {0}; stroke: {0}; stroke-dasharray: {1}", marker.color, marker.dash.dash_array())}
+                class="grid-marker-range"
+            >
+                <title>{marker.label.clone()}</title>
+            </rect>
+        }
+    } else {
+        html! {
+            <line
+                x1={marker.x_1.to_string()}
+                y1={0}
+                x2={marker.x_1.to_string()}
+                y2={height.to_string()}
+                style={format!("stroke: {}; stroke-dasharray: {}", marker.color, marker.dash.dash_array())}
+                class="grid-marker-line"
+            >
+                <title>{marker.label.clone()}</title>
+            </line>
+        }
+    }
+}
+
+/// Label of the marker in the calendar header, ranges are labelled at their middle.
+fn calendar_marker(marker: &GridMarker, header_height: f64, rtl: bool) -> Html {
+    let (x, class) = if marker.is_range {
+        ((marker.x_1 + marker.x_2) * 0.5, "calendar-marker-text calendar-marker-range-text")
+    } else if rtl {
+        (marker.x_1 - 3.0, "calendar-marker-text")
+    } else {
+        (marker.x_1 + 3.0, "calendar-marker-text")
+    };
+
+    html! {
+        <g class="calendar-marker">
+            <line
+                x1={marker.x_1.to_string()}
+                y1={(header_height * 0.5).to_string()}
+                x2={marker.x_1.to_string()}
+                y2={header_height.to_string()}
+                style={format!("stroke: {}; stroke-dasharray: {}", marker.color, marker.dash.dash_array())}
+                class="calendar-marker-tick"
+            />
+            <text
+                x={x.to_string()}
+                y={(header_height * 0.5 - 3.0).to_string()}
+                style={format!("fill: {}", marker.color)}
+                class={class}
+            >
+                {marker.label.clone()}
+            </text>
+        </g>
+    }
+}
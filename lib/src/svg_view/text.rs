@@ -0,0 +1,81 @@
+use std::cell::RefCell;
+
+thread_local! {
+    static CONTEXT: RefCell<Option<web_sys::CanvasRenderingContext2d>> = const { RefCell::new(None) };
+}
+
+const ELLIPSIS: &str = "…";
+
+/// Rendered width of `text` in px, measured on an offscreen canvas.
+/// Falls back to an average glyph width estimate when no canvas is available.
+pub(crate) fn text_width(text: &str, font_size: &str, font_family: &str) -> f64 {
+    let font = format!("{} {}", font_size, font_family);
+    CONTEXT
+        .with(|cell| {
+            let mut context = cell.borrow_mut();
+            if context.is_none() {
+                *context = canvas_context();
+            }
+            context.as_ref().and_then(|ctx| {
+                ctx.set_font(&font);
+                ctx.measure_text(text).map(|metrics| metrics.width()).ok()
+            })
+        })
+        .unwrap_or_else(|| estimate_width(text, font_size))
+}
+
+/// Cuts `text` so it fits into `max_width`, appending an ellipsis when anything was cut.
+pub(crate) fn truncate(text: &str, max_width: f64, font_size: &str, font_family: &str) -> String {
+    if text_width(text, font_size, font_family) <= max_width {
+        return text.to_owned();
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    // longest prefix which still fits together with the ellipsis
+    let (mut low, mut high) = (0, chars.len());
+    while low < high {
+        let mid = (low + high).div_ceil(2);
+        let candidate = format!("{}{}", chars[..mid].iter().collect::<String>(), ELLIPSIS);
+        if text_width(&candidate, font_size, font_family) <= max_width {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    if low == 0 && text_width(ELLIPSIS, font_size, font_family) > max_width {
+        String::new()
+    } else {
+        format!("{}{}", chars[..low].iter().collect::<String>(), ELLIPSIS)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn canvas_context() -> Option<web_sys::CanvasRenderingContext2d> {
+    use wasm_bindgen::JsCast;
+
+    web_sys::window()?
+        .document()?
+        .create_element("canvas")
+        .ok()?
+        .dyn_into::<web_sys::HtmlCanvasElement>()
+        .ok()?
+        .get_context("2d")
+        .ok()??
+        .dyn_into::<web_sys::CanvasRenderingContext2d>()
+        .ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn canvas_context() -> Option<web_sys::CanvasRenderingContext2d> {
+    None
+}
+
+fn estimate_width(text: &str, font_size: &str) -> f64 {
+    let size = font_size
+        .trim_end_matches("px")
+        .trim()
+        .parse::<f64>()
+        .unwrap_or(14.0);
+    text.chars().count() as f64 * size * 0.6
+}
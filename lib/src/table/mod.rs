@@ -1,14 +1,76 @@
-use yew::{html, Component, Context, Html};
+use std::cell::RefCell;
+use std::fmt::Write;
+use std::rc::Rc;
+
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use gloo_events::EventListener;
+use wasm_bindgen::JsCast;
+use yew::{
+    classes, html, Callback, Component, Context, DragEvent, FocusEvent, Html, InputEvent,
+    KeyboardEvent, MouseEvent, TargetCast, WheelEvent,
+};
+
+use super::schemas::{
+    ColumnAlign, DropPlace, GroupRow, LaneRow, Row, RowDrop, TableColumn, TableHeaderCtx,
+    TableResize, TableRowCtx, Task, TaskType,
+};
+use super::time_zone;
+
+/// Columns editable on double click, unless they have a `value` of their own.
+const EDITABLE: [&str; 4] = ["name", "start", "end", "progress"];
 
 #[derive(Default)]
 pub struct Table {
-    horizontal_container_ref: yew::NodeRef,
     task_list_ref: yew::NodeRef,
     table_props: super::schemas::TableProps,
+    // width of a column, or of the task list for `None`, while it is resized
+    preview: Option<(Option<String>, f64)>,
+    resizing: Rc<RefCell<Option<Resizing>>>,
+    // a resize goes on outside of the table
+    listeners: Vec<EventListener>,
+    editing: Option<Editing>,
+    editor_ref: yew::NodeRef,
+    // the editor takes the focus once, when the edit starts
+    focus_pending: bool,
+    // task of the dragged row and where it would be dropped
+    dragged: Option<usize>,
+    drop_at: Option<(usize, DropPlace)>,
+}
+
+/// Cell being edited, `value` as typed and why it was rejected.
+struct Editing {
+    task: usize,
+    column: String,
+    value: String,
+    error: Option<String>,
+}
+
+/// Resize in progress of a column, or of the task list for `None`, from pointer `x` and `width`.
+#[derive(Clone)]
+struct Resizing {
+    column: Option<String>,
+    x: f64,
+    width: f64,
+}
+
+pub enum TableMsg {
+    /// Pointer at `x` while resizing, `done` on release.
+    Resize { x: f64, done: bool },
+    /// Starts editing a cell of the task with the index.
+    Edit(usize, String),
+    Input(String),
+    /// Accepts the edit, it stays open with a message if rejected.
+    Commit,
+    Cancel,
+    /// Starts dragging the row of the task with the index.
+    DragStart(usize),
+    DragOver(usize, DropPlace),
+    Drop,
+    DragEnd,
 }
 
 impl Component for Table {
-    type Message = ();
+    type Message = TableMsg;
     type Properties = super::schemas::TableProps;
 
     fn create(ctx: &Context<Self>) -> Self {
@@ -18,59 +80,323 @@ impl Component for Table {
         }
     }
 
-    fn view(&self, _ctx: &Context<Self>) -> Html {
-        let table_content: Html = self.table_props.tasks.clone().unwrap_or_default().iter()
-            .map(|task|  {
-                let expander = if task.hide_children.unwrap_or(true) { "▼" } else { "▶" };   
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let (x, done) = match msg {
+            TableMsg::Resize { x, done } => (x, done),
+            TableMsg::Edit(task, column) => {
+                let Some(task_value) = self.table_props.tasks.as_ref().and_then(|it| it.get(task))
+                else {
+                    return false;
+                };
+                let value = self.edit_value(&column, task_value);
+                self.editing = Some(Editing { task, column, value, error: None });
+                self.focus_pending = true;
+                return true;
+            }
+            TableMsg::Input(value) => {
+                let Some(editing) = &mut self.editing else {
+                    return false;
+                };
+                editing.value = value;
+                editing.error = None;
+                return true;
+            }
+            TableMsg::Commit => return self.commit(),
+            TableMsg::Cancel => return self.editing.take().is_some(),
+            TableMsg::DragStart(task) => {
+                self.dragged = Some(task);
+                return false;
+            }
+            TableMsg::DragOver(target, place) => {
+                let drop_at = self.dragged.filter(|it| *it != target).map(|_| (target, place));
+                return std::mem::replace(&mut self.drop_at, drop_at) != drop_at;
+            }
+            TableMsg::Drop => {
+                if let (Some(task), Some((target, place)), Some(on_row_drop)) =
+                    (self.dragged.take(), self.drop_at.take(), &self.table_props.on_row_drop)
+                {
+                    on_row_drop.emit(RowDrop { task, target, place });
+                }
+                return true;
+            }
+            TableMsg::DragEnd => {
+                self.dragged = None;
+                return self.drop_at.take().is_some();
+            }
+        };
+        let Some(resizing) = self.resizing.borrow().clone() else {
+            return false;
+        };
+        // columns run from right to left in rtl
+        let dx = if self.table_props.rtl { resizing.x - x } else { x - resizing.x };
+        let min = if resizing.column.is_some() { 30.0 } else { 60.0 };
+        let width = (resizing.width + dx).max(min).round();
+        self.preview = Some((resizing.column.clone(), width));
+        if done {
+            *self.resizing.borrow_mut() = None;
+            if let Some(on_resize) = &self.table_props.on_resize {
+                on_resize.emit(match resizing.column {
+                    Some(id) => TableResize::Column(id, width),
+                    None => TableResize::Table(width),
+                });
+            }
+        }
+        true
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        self.table_props = ctx.props().clone();
+        self.preview = None;
+        // the edited task may be gone
+        if self.table_props.tasks != old_props.tasks {
+            self.editing = None;
+        }
+        true
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, first_render: bool) {
+        if self.focus_pending {
+            if let Some(editor) = self.editor_ref.cast::<web_sys::HtmlElement>() {
+                self.focus_pending = false;
+                let _ = editor.focus();
+            }
+        }
+        if !first_render {
+            return;
+        }
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let listener = |event_type: &'static str, done: bool| {
+            let resizing = self.resizing.clone();
+            let link = ctx.link().clone();
+            EventListener::new(&window, event_type, move |event| {
+                let Some(event) = event.dyn_ref::<web_sys::MouseEvent>() else {
+                    return;
+                };
+                if resizing.borrow().is_some() {
+                    link.send_message(TableMsg::Resize { x: event.client_x() as f64, done });
+                }
+            })
+        };
+        self.listeners = vec![listener("mousemove", false), listener("mouseup", true)];
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let tasks = self.table_props.tasks.clone().unwrap_or_default();
+        let mut rows = self.table_props.visible_rows.clone();
+        // a group packed into lanes has one line over all its lanes, render it whole
+        let lane = |row: usize| match self.table_props.rows.get(row) {
+            Some(Row::Lane(lane)) => Some((lane.lane, lane.lanes)),
+            _ => None,
+        };
+        if let Some((lane, _)) = lane(rows.start) {
+            rows.start -= lane;
+        }
+        if let Some((lane, lanes)) = rows.end.checked_sub(1).and_then(lane) {
+            rows.end += lanes - lane - 1;
+        }
+        let row_height = self.table_props.row_height.unwrap_or_default();
+        let rows_after = self.table_props.rows.len().saturating_sub(rows.end);
+        let columns: Vec<TableColumn> = self
+            .table_props
+            .columns
+            .iter()
+            .filter(|it| !it.hidden.unwrap_or(false))
+            .cloned()
+            .collect();
+        let table_content: Html = self.table_props.rows.iter()
+            .skip(rows.start)
+            .take(rows.len())
+            .map(|row|  {
+                let (index, task) = match row {
+                    Row::Group(group) => return self.group_row(&columns, group),
+                    Row::Lane(lane) => return self.lane_row(&columns, lane),
+                    Row::Task(i) => (*i, &tasks[*i]),
+                };
+                let expander = if task.hide_children.unwrap_or(true) {
+                    "▼"
+                } else if self.table_props.rtl {
+                    "◀"
+                } else {
+                    "▶"
+                };   
+                let selected = self.table_props.selected_task_id.is_some()
+                    && self.table_props.selected_task_id == task.id;
+                let cells: Html = match &self.table_props.row_content {
+                    Some(row_content) => row_content.emit(TableRowCtx {
+                        task: task.clone(),
+                        index,
+                        columns: columns.clone(),
+                        wbs: self.table_props.wbs.get(index).cloned().unwrap_or_default(),
+                        level: self.table_props.levels.get(index).copied().unwrap_or(0),
+                        selected,
+                    }),
+                    None => columns
+                        .iter()
+                        .map(|column| {
+                            if let Some(editing) = self.editing.as_ref().filter(|it| {
+                                it.task == index && it.column == column.id
+                            }) {
+                                return self.editor(ctx, column, editing);
+                            }
+                            let value = self.value(column, index, task);
+                            let editable = column.value.is_none()
+                                && self.table_props.on_task_edit.is_some()
+                                && EDITABLE.contains(&column.id.as_str());
+                            let ondblclick = editable.then(|| {
+                                let id = column.id.clone();
+                                ctx.link().callback(move |_| TableMsg::Edit(index, id.clone()))
+                            });
+                            if column.id != "name" {
+                                let content = html! { {value.clone()} };
+                                return self.editable_cell(column, value, content, ondblclick);
+                            }
+                            // tasks of a project are indented under it
+                            let level = self.table_props.levels.get(index).copied().unwrap_or(0);
+                            self.editable_cell(column, value.clone(), html! {
+                                <div
+                                    class="gantt-table-name-wrapper"
+                                    style={(level > 0).then(|| {
+                                        format!("padding-inline-start: {}em", level)
+                                    })}
+                                >
+                                    <div
+                                    class={if task.hide_children.unwrap_or(true) { "table-expander" } else { "table-empty-expander" } }
+                                    // onClick={() => onExpanderClick(t)}
+                                    >
+                                        {expander}
+                                    </div>
+                                    <div>{value}</div>
+                                </div>
+                            }, ondblclick)
+                        })
+                        .collect(),
+                };
+                let dimmed = self.table_props.dimmed.contains(&index);
+                let drop_at = match self.drop_at.filter(|(target, _)| *target == index) {
+                    Some((_, DropPlace::Before)) => "gantt-table-drop-before",
+                    Some((_, DropPlace::After)) => "gantt-table-drop-after",
+                    Some((_, DropPlace::Into)) => "gantt-table-drop-into",
+                    None => "",
+                };
+                let class = classes!(
+                    "gantt-table-row",
+                    dimmed.then_some("gantt-table-row-dimmed"),
+                    selected.then_some("gantt-table-row-selected"),
+                    drop_at
+                );
+                // rows are dragged in their unsorted order
+                let draggable = self.table_props.on_row_drop.is_some()
+                    && self.table_props.sort.is_none()
+                    && self.editing.is_none();
+                let link = ctx.link();
+                let project = task.type_ == Some(TaskType::Project);
                 html!{
                     <div
-                        class="gantt-table-row"
-                        style={format!("height: {}px;", self.table_props.row_height.clone().unwrap() - 2.0)}
+                        class={class}
+                        style={format!("height: {}px;", self.table_props.row_height.unwrap() - 2.0)}
                         key={format!("{}row", task.id.clone().unwrap())}
+                        onclick={self.table_props.on_select.clone().map(|on_select| {
+                            on_select.reform(move |_: MouseEvent| index)
+                        })}
+                        draggable={draggable.then_some("true")}
+                        ondragstart={draggable.then(|| link.callback(move |e: DragEvent| {
+                            if let Some(data) = e.data_transfer() {
+                                data.set_effect_allowed("move");
+                                let _ = data.set_data("text/plain", &index.to_string());
+                            }
+                            TableMsg::DragStart(index)
+                        }))}
+                        ondragover={draggable.then(|| link.batch_callback(move |e: DragEvent| {
+                            let row = e.current_target()?.dyn_into::<web_sys::Element>().ok()?;
+                            let rect = row.get_bounding_client_rect();
+                            e.prevent_default();
+                            // the middle of a project row drops into the project
+                            let y = (e.client_y() as f64 - rect.top()) / rect.height().max(1.0);
+                            let place = if project && (0.25..0.75).contains(&y) {
+                                DropPlace::Into
+                            } else if y < 0.5 {
+                                DropPlace::Before
+                            } else {
+                                DropPlace::After
+                            };
+                            Some(TableMsg::DragOver(index, place))
+                        }))}
+                        ondrop={draggable.then(|| link.callback(|e: DragEvent| {
+                            e.prevent_default();
+                            TableMsg::Drop
+                        }))}
+                        ondragend={draggable.then(|| link.callback(|_| TableMsg::DragEnd))}
                     >
+                        {cells}
+                    </div>
+                }
+            }).collect();
+
+        // clicking a header sorts by the column, dragging the separator after it resizes it
+        let header_items: Html = columns
+            .iter()
+            .map(|column| {
+                let width = self.width(column);
+                let onclick = self.table_props.on_sort.clone().map(|on_sort| {
+                    let id = column.id.clone();
+                    on_sort.reform(move |_: MouseEvent| id.clone())
+                });
+                let sorted = match &self.table_props.sort {
+                    Some((id, false)) if *id == column.id => " ▲",
+                    Some((id, true)) if *id == column.id => " ▼",
+                    _ => "",
+                };
+                html! {
+                    <>
                         <div
-                        class="gantt-table-cell"
-                        style={format!("min-width: {}; max-width: {}",
-                                self.table_props.row_width.clone().unwrap(),
-                                self.table_props.row_width.clone().unwrap())
-                         }
-                        title={task.name.clone().unwrap()}
-                        >
-                        <div class="gantt-table-name-wrapper">
-                            <div
-                            class={if task.hide_children.unwrap_or(true) { "table-expander" } else { "table-empty-expander" } }
-                            // onClick={() => onExpanderClick(t)}
-                            >
-                                {expander}
-                            </div>
-                            <div>{task.name.clone().unwrap()}</div>
-                        </div>
-                        </div>
-                        <div
-                            class="gantt-table-cell"
-                            style={format!("min-width: {}; max-width: {}",
-                                    self.table_props.row_width.clone().unwrap(),
-                                    self.table_props.row_width.clone().unwrap())
-                            }
+                            class={classes!(
+                                "gantt-table-header-item",
+                                onclick.is_some().then_some("gantt-table-header-sortable")
+                            )}
+                            style={format!("min-width: {0}; max-width: {0};", width)}
+                            onclick={onclick}
                         >
-                         {task.start.clone().unwrap()}
+                            {column.header.clone().unwrap_or_default()}{sorted}
                         </div>
                         <div
-                            class="gantt-table-cell"
-                            style={format!("min-width: {}; max-width: {}",
-                                    self.table_props.row_width.clone().unwrap(),
-                                    self.table_props.row_width.clone().unwrap())
-                            }
-                        >
-                         {task.end.clone().unwrap()}
-                        </div>
-                    </div>
+                            class="gantt-table-header-separator"
+                            style={format!("height: {}px; margin-top: {}px",
+                                 self.table_props.header_height * 0.5,
+                                 self.table_props.header_height * 0.2)}
+                            onmousedown={self.start_resize(Some(column.id.clone()))}
+                        />
+                    </>
                 }
-            }).collect();
+            })
+            .collect();
+        let table_width = match &self.preview {
+            Some((None, width)) => Some(*width),
+            _ => self.table_props.table_width,
+        };
 
+        // the table body has no scrollbar, it scrolls along with the chart
+        let on_wheel = (self.table_props.gantt_height > 0.0).then(|| {
+            let scroll = self.table_props.scroll_ref.clone();
+            Callback::from(move |e: WheelEvent| {
+                if e.ctrl_key() {
+                    return;
+                }
+                if let Some(scroll) = scroll.cast::<web_sys::Element>() {
+                    e.prevent_default();
+                    scroll.set_scroll_top(scroll.scroll_top() + e.delta_y() as i32);
+                }
+            })
+        });
 
         html! {
-            <div ref={self.task_list_ref.clone()}>
+          <>
+            <div
+                ref={self.task_list_ref.clone()}
+                class="gantt-task-list"
+                style={table_width.map(|width| format!("width: {0}px; min-width: {0}px", width))}
+            >
                 <div
                     class="gantt-table"
                     style={
@@ -83,42 +409,26 @@ impl Component for Table {
                         class="gantt-table-header"
                         style={format!("height: {}px;", self.table_props.header_height - 2.0)}
                     >
-                        <div
-                            class="gantt-table-header-item"       
-                            style={format!("min-width: {};", self.table_props.row_width.clone().unwrap())}
-                        >
-                            {"Name"}
-                        </div>
-                        <div
-                            class="gantt-table-header-separator"
-                            style={format!("height: {}px; margin-top: {}px",
-                                 self.table_props.header_height * 0.5,
-                                 self.table_props.header_height * 0.2)}
-                        />
-                        <div
-                            class="gantt-table-header-item"
-                            style={format!("min-width:{};", self.table_props.row_width.clone().unwrap())}
-                        >
-                            {"From"}
-                        </div>
-                        <div
-                            class="gantt-table-header-separator"
-                            style={format!("height: {}px; margin-top: {}px",
-                                 self.table_props.header_height * 0.5,
-                                 self.table_props.header_height * 0.25)}
-                        />
-                        <div
-                            class="gantt-table-header-item"
-                            style={format!("min-width:{};", self.table_props.row_width.clone().unwrap())}
-                        >
-                            {"To"}
-                        </div>
+                        {match &self.table_props.header_content {
+                            Some(header_content) => header_content.emit(TableHeaderCtx {
+                                columns: columns.clone(),
+                                sort: self.table_props.sort.clone(),
+                            }),
+                            None => header_items,
+                        }}
                     </div>
                 </div>
                 <div
-                    ref={self.horizontal_container_ref.clone()}
+                    ref={self.table_props.body_ref.clone()}
                     class="horizontal-container"
-                    // style={format!("height:{};", self.table_props.gantt_height)}
+                    style={
+                        if self.table_props.gantt_height > 0.0 {
+                            format!("height: {}px;", self.table_props.gantt_height)
+                        } else {
+                            String::new()
+                        }
+                    }
+                    onwheel={on_wheel}
                 >
                     <div
                         class="gantt-table-wrapper"
@@ -128,10 +438,408 @@ impl Component for Table {
                                 self.table_props.font_size.clone().unwrap())
                         }
                     >
+                        // rows outside the viewport only keep their height
+                        <span
+                            class="gantt-table-spacer"
+                            style={format!("height: {}px", row_height * (rows.start as f64))}
+                        />
                         {table_content}
+                        <span
+                            class="gantt-table-spacer"
+                            style={format!("height: {}px", row_height * (rows_after as f64))}
+                        />
                     </div>
                 </div>
             </div>
+            <div class="gantt-splitter" onmousedown={self.start_resize(None)} />
+          </>
+        }
+    }
+}
+
+impl Table {
+    /// CSS width of the column.
+    fn width(&self, column: &TableColumn) -> String {
+        if let Some((Some(id), width)) = &self.preview {
+            if *id == column.id {
+                return format!("{}px", width);
+            }
+        }
+        column
+            .width
+            .clone()
+            .or_else(|| self.table_props.row_width.clone())
+            .unwrap_or_default()
+    }
+
+    /// Starts resizing a column, or the task list for `None`, from its rendered width.
+    fn start_resize(&self, column: Option<String>) -> Callback<MouseEvent> {
+        let resizing = self.resizing.clone();
+        let task_list = self.task_list_ref.clone();
+        Callback::from(move |e: MouseEvent| {
+            if e.button() != 0 {
+                return;
+            }
+            let element = match &column {
+                // header item before the separator
+                Some(_) => e
+                    .target()
+                    .and_then(|it| it.dyn_into::<web_sys::Element>().ok())
+                    .and_then(|it| it.previous_element_sibling()),
+                None => task_list.cast::<web_sys::Element>(),
+            };
+            let Some(element) = element else {
+                return;
+            };
+            e.prevent_default();
+            *resizing.borrow_mut() = Some(Resizing {
+                column: column.clone(),
+                x: e.client_x() as f64,
+                width: element.get_bounding_client_rect().width(),
+            });
+        })
+    }
+
+    fn cell(&self, column: &TableColumn, title: String, content: Html) -> Html {
+        self.editable_cell(column, title, content, None)
+    }
+
+    /// Cell opening its editor on double click.
+    fn editable_cell(
+        &self,
+        column: &TableColumn,
+        title: String,
+        content: Html,
+        ondblclick: Option<Callback<MouseEvent>>,
+    ) -> Html {
+        let align = match column.align.unwrap_or_default() {
+            ColumnAlign::Start => "start",
+            ColumnAlign::Center => "center",
+            ColumnAlign::End => "end",
+        };
+        let width = self.width(column);
+        html! {
+            <div
+                class="gantt-table-cell"
+                style={format!("min-width: {0}; max-width: {0}; text-align: {1}", width, align)}
+                title={title}
+                ondblclick={ondblclick}
+            >
+                {content}
+            </div>
+        }
+    }
+
+    /// Cell text of a task.
+    fn value(&self, column: &TableColumn, index: usize, task: &Task) -> String {
+        if column.id == "wbs" && column.value.is_none() {
+            return self.table_props.wbs.get(index).cloned().unwrap_or_default();
+        }
+        let props = &self.table_props;
+        let display = props.display_date_format.as_deref();
+        cell_value(column, task, &props.date_format, &props.time_zone, display)
+    }
+
+    /// Initial editor value of a cell, dates as the picker takes them.
+    fn edit_value(&self, column: &str, task: &Task) -> String {
+        let props = &self.table_props;
+        let date = match column {
+            "name" => return task.name.clone().unwrap_or_default(),
+            "progress" => return task.progress.unwrap_or_default().to_string(),
+            "start" => &task.start,
+            _ => &task.end,
+        };
+        let (_, format, _) = picker(&props.date_format);
+        date.as_deref()
+            .and_then(|it| time_zone::parse_date(it, &props.date_format, &props.time_zone).ok())
+            .map(|it| props.time_zone.to_local(it).format(format).to_string())
+            .unwrap_or_default()
+    }
+
+    /// Input of the edited cell. Enter accepts, Escape cancels, leaving the input accepts too.
+    fn editor(&self, ctx: &Context<Self>, column: &TableColumn, editing: &Editing) -> Html {
+        let (kind, step) = match column.id.as_str() {
+            "name" => ("text", None),
+            "progress" => ("number", Some("1")),
+            _ => {
+                let (kind, _, step) = picker(&self.table_props.date_format);
+                (kind, Some(step))
+            }
+        };
+        let progress = column.id == "progress";
+        let link = ctx.link();
+        let oninput = link.callback(|e: InputEvent| {
+            TableMsg::Input(e.target_unchecked_into::<web_sys::HtmlInputElement>().value())
+        });
+        let onkeydown = link.batch_callback(|e: KeyboardEvent| match e.key().as_str() {
+            "Enter" => Some(TableMsg::Commit),
+            "Escape" => Some(TableMsg::Cancel),
+            _ => None,
+        });
+        let onblur = link.callback(|_: FocusEvent| TableMsg::Commit);
+        let content = html! {
+            <>
+                <input
+                    ref={self.editor_ref.clone()}
+                    class={classes!(
+                        "gantt-table-input",
+                        editing.error.is_some().then_some("invalid")
+                    )}
+                    type={kind}
+                    value={editing.value.clone()}
+                    step={step}
+                    min={progress.then_some("0")}
+                    max={progress.then_some("100")}
+                    oninput={oninput}
+                    onkeydown={onkeydown}
+                    onblur={onblur}
+                />
+                if let Some(error) = &editing.error {
+                    <div class="gantt-table-edit-error">{error.clone()}</div>
+                }
+            </>
+        };
+        self.cell(column, editing.error.clone().unwrap_or_default(), content)
+    }
+
+    /// Passes an accepted edit on, a rejected one stays open with the reason.
+    fn commit(&mut self) -> bool {
+        let Some(mut editing) = self.editing.take() else {
+            return false;
+        };
+        let task = self.table_props.tasks.as_ref().and_then(|it| it.get(editing.task));
+        let result = match task {
+            Some(task) => self
+                .edited(task, &editing)
+                .map(|changed| (*task != changed).then_some(changed)),
+            None => Ok(None),
+        };
+        match result {
+            Ok(Some(changed)) => {
+                if let Some(on_task_edit) = &self.table_props.on_task_edit {
+                    on_task_edit.emit((editing.task, changed));
+                }
+            }
+            Ok(None) => {}
+            Err(err) => {
+                editing.error = Some(err);
+                self.editing = Some(editing);
+            }
+        }
+        true
+    }
+
+    /// Task with the edit applied, if both the value and the change are accepted. A date moves
+    /// the start of the first or the end of the last work segment.
+    fn edited(&self, task: &Task, editing: &Editing) -> Result<Task, String> {
+        let props = &self.table_props;
+        let (fmt, tz) = (&props.date_format, &props.time_zone);
+        let value = editing.value.trim();
+        let mut changed = task.clone();
+        match editing.column.as_str() {
+            "name" if value.is_empty() => return Err("Enter a name".to_owned()),
+            "name" => changed.name = Some(value.to_owned()),
+            "progress" => {
+                let progress = value
+                    .trim_end_matches('%')
+                    .trim()
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|it| (0.0..=100.0).contains(it))
+                    .ok_or("Enter a progress from 0 to 100")?;
+                changed.progress = Some(progress);
+            }
+            column => {
+                let date = parse_picker(value).ok_or("Enter a date")?;
+                let date = tz.to_utc(date);
+                let start = column == "start";
+                let mut segments = task.segment_dates(fmt, tz);
+                let segment = if start { segments.first_mut() } else { segments.last_mut() };
+                match segment {
+                    Some(segment) if start => segment.0 = date,
+                    Some(segment) => segment.1 = date,
+                    None if start => changed.start = Some(time_zone::format_date(date, fmt, tz)),
+                    None => changed.end = Some(time_zone::format_date(date, fmt, tz)),
+                }
+                if !segments.is_empty() {
+                    changed.set_segment_dates(&segments, fmt, tz);
+                }
+            }
+        }
+        super::check_task_change(&props.validate_task_change, task, &changed, fmt, tz)?;
+        Ok(changed)
+    }
+
+    /// Header row of a group, its expander collapses the group.
+    fn group_row(&self, columns: &[TableColumn], group: &GroupRow) -> Html {
+        let props = &self.table_props;
+        let expander = if !group.collapsed {
+            "▼"
+        } else if props.rtl {
+            "◀"
+        } else {
+            "▶"
+        };
+        let onclick = props.on_group_toggle.clone().map(|on_group_toggle| {
+            let key = group.key.clone();
+            on_group_toggle.reform(move |_| key.clone())
+        });
+        let label = format!("{} ({})", group.label, group.tasks.len());
+        let cells: Html = columns
+            .iter()
+            .map(|column| match column.id.as_str() {
+                "name" => self.cell(column, label.clone(), html! {
+                    <div class="gantt-table-name-wrapper">
+                        <div class="table-expander" onclick={onclick.clone()}>{expander}</div>
+                        <div>{label.clone()}</div>
+                    </div>
+                }),
+                "start" => self.cell(column, group.start.clone(), html! { {group.start.clone()} }),
+                "end" => self.cell(column, group.end.clone(), html! { {group.end.clone()} }),
+                _ => self.cell(column, String::new(), html! {}),
+            })
+            .collect();
+
+        html! {
+            <div
+                class="gantt-table-row gantt-table-group-row"
+                style={format!("height: {}px;", props.row_height.unwrap_or_default() - 2.0)}
+                key={format!("Group{}", group.key)}
+            >
+                {cells}
+            </div>
+        }
+    }
+
+    /// Line of a group packed into lanes, as high as all of its lanes.
+    fn lane_row(&self, columns: &[TableColumn], lane: &LaneRow) -> Html {
+        if lane.lane > 0 {
+            return html! {};
+        }
+        let props = &self.table_props;
+        let height = props.row_height.unwrap_or_default() * (lane.lanes as f64) - 2.0;
+        let label = format!("{} ({})", lane.label, lane.count);
+        let cells: Html = columns
+            .iter()
+            .map(|column| match column.id.as_str() {
+                "name" => self.cell(column, label.clone(), html! { {label.clone()} }),
+                _ => self.cell(column, String::new(), html! {}),
+            })
+            .collect();
+
+        html! {
+            <div
+                class="gantt-table-row gantt-table-lane-row"
+                style={format!("height: {}px;", height)}
+                key={format!("Lane{}", lane.key)}
+            >
+                {cells}
+            </div>
+        }
+    }
+}
+
+/// Cell text of a task, `fmt` and `tz` are the ones of the task dates, `display` the style the
+/// dates are shown in, see [`time_zone::display_date`].
+pub(crate) fn cell_value(
+    column: &TableColumn,
+    task: &Task,
+    fmt: &str,
+    tz: &time_zone::DisplayTimeZone,
+    display: Option<&str>,
+) -> String {
+    if let Some(value) = &column.value {
+        return value.emit(task.clone());
+    }
+    match column.id.as_str() {
+        "name" => task.name.clone().unwrap_or_default(),
+        "start" | "end" => {
+            let value = if column.id == "start" { &task.start } else { &task.end };
+            let value = value.clone().unwrap_or_default();
+            match display.zip(time_zone::parse_date(&value, fmt, tz).ok()) {
+                Some((style, date)) => time_zone::display_date(date, style, tz),
+                None => value,
+            }
+        }
+        "duration" => duration(task.work_time(fmt, tz)),
+        "progress" => format!("{}%", task.progress.unwrap_or_default().round()),
+        "type" => match task.type_ {
+            Some(TaskType::Milestone) => "milestone",
+            Some(TaskType::Project) => "project",
+            Some(TaskType::Task) => "task",
+            None => "",
         }
+        .to_owned(),
+        "dependencies" => task.dependencies.clone().unwrap_or_default().join(", "),
+        id => task.field(column.field.as_deref().unwrap_or(id)).unwrap_or_default(),
+    }
+}
+
+/// Input type, value format and step of the date picker for dates written in `fmt`. Without a
+/// time of day in `fmt` it picks days, without seconds minutes.
+fn picker(fmt: &str) -> (&'static str, &'static str, &'static str) {
+    let text = |date: NaiveDateTime| {
+        let mut value = String::new();
+        write!(value, "{}", date.format(fmt)).ok().map(|_| value)
+    };
+    let day = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap_or_default();
+    let at = |h, m, s| day.and_hms_opt(h, m, s).map(text).unwrap_or_default();
+    if at(0, 0, 0).is_some() && at(0, 0, 0) == at(1, 2, 0) {
+        ("date", "%Y-%m-%d", "1")
+    } else if at(1, 2, 0).is_some() && at(1, 2, 0) == at(1, 2, 3) {
+        ("datetime-local", "%Y-%m-%dT%H:%M", "60")
+    } else {
+        ("datetime-local", "%Y-%m-%dT%H:%M:%S", "1")
+    }
+}
+
+/// Wall clock time of a picker value, browsers leave out zero seconds.
+fn parse_picker(value: &str) -> Option<NaiveDateTime> {
+    ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()?;
+            date.and_hms_opt(0, 0, 0)
+        })
+}
+
+/// Duration in the largest units that keep it exact, e.g. `3d 4h` or `2w`.
+fn duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    let (days, hours, minutes) = (minutes / 1440, minutes % 1440 / 60, minutes % 60);
+    if days > 0 && days % 7 == 0 && hours == 0 && minutes == 0 {
+        return format!("{}w", days / 7);
+    }
+    let parts: Vec<String> = [(days, "d"), (hours, "h"), (minutes, "m")]
+        .iter()
+        .filter(|(value, _)| *value > 0)
+        .map(|(value, unit)| format!("{}{}", value, unit))
+        .collect();
+    if parts.is_empty() {
+        "0d".to_owned()
+    } else {
+        parts.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations_use_the_largest_exact_units() {
+        assert_eq!(duration(Duration::days(3) + Duration::hours(4)), "3d 4h");
+        assert_eq!(duration(Duration::days(14)), "2w");
+        assert_eq!(duration(Duration::days(7) + Duration::hours(1)), "7d 1h");
+        assert_eq!(duration(Duration::minutes(90)), "1h 30m");
+        assert_eq!(duration(Duration::days(1)), "1d");
+    }
+
+    #[test]
+    fn empty_durations_are_zero_days() {
+        assert_eq!(duration(Duration::zero()), "0d");
+        assert_eq!(duration(Duration::seconds(59)), "0d");
+        assert_eq!(duration(Duration::hours(-3)), "0d");
     }
 }
@@ -0,0 +1,390 @@
+use serde::{Deserialize, Serialize};
+
+use super::macros;
+use super::schemas::StylingOption;
+
+#[derive(Clone, PartialEq, Eq, Debug, Default, Deserialize, Serialize)]
+pub enum ThemePreset {
+    #[default]
+    #[serde(rename = "light")]
+    Light,
+    #[serde(rename = "dark")]
+    Dark,
+    #[serde(rename = "high-contrast")]
+    HighContrast,
+}
+
+/// Colours and fonts of every chart element.
+///
+/// Each value is exposed as a `--gantt-*` CSS custom property on the chart wrapper, so host
+/// pages can restyle the chart from their own stylesheet, e.g.
+/// `.wrapper { --gantt-bar-progress-color: teal; }`.
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct Theme {
+    #[serde(rename = "barBackgroundColor")]
+    pub bar_background_color: String,
+    #[serde(rename = "barBackgroundSelectedColor")]
+    pub bar_background_selected_color: String,
+    #[serde(rename = "barProgressColor")]
+    pub bar_progress_color: String,
+    #[serde(rename = "barProgressSelectedColor")]
+    pub bar_progress_selected_color: String,
+    #[serde(rename = "projectBackgroundColor")]
+    pub project_background_color: String,
+    #[serde(rename = "projectBackgroundSelectedColor")]
+    pub project_background_selected_color: String,
+    #[serde(rename = "projectProgressColor")]
+    pub project_progress_color: String,
+    #[serde(rename = "projectProgressSelectedColor")]
+    pub project_progress_selected_color: String,
+    #[serde(rename = "milestoneBackgroundColor")]
+    pub milestone_background_color: String,
+    #[serde(rename = "milestoneBackgroundSelectedColor")]
+    pub milestone_background_selected_color: String,
+    #[serde(rename = "barLabelColor")]
+    pub bar_label_color: String,
+    #[serde(rename = "barLabelOutsideColor")]
+    pub bar_label_outside_color: String,
+    #[serde(rename = "arrowColor")]
+    pub arrow_color: String,
+    #[serde(rename = "todayColor")]
+    pub today_color: String,
+    #[serde(rename = "markerColor")]
+    pub marker_color: String,
+    #[serde(rename = "gridRowColor")]
+    pub grid_row_color: String,
+    #[serde(rename = "gridRowEvenColor")]
+    pub grid_row_even_color: String,
+    #[serde(rename = "gridRowLineColor")]
+    pub grid_row_line_color: String,
+    #[serde(rename = "gridTickColor")]
+    pub grid_tick_color: String,
+    #[serde(rename = "calendarBackgroundColor")]
+    pub calendar_background_color: String,
+    #[serde(rename = "calendarBorderColor")]
+    pub calendar_border_color: String,
+    #[serde(rename = "calendarTextColor")]
+    pub calendar_text_color: String,
+    #[serde(rename = "calendarTopTextColor")]
+    pub calendar_top_text_color: String,
+    #[serde(rename = "tableTextColor")]
+    pub table_text_color: String,
+    #[serde(rename = "tableBackgroundColor")]
+    pub table_background_color: String,
+    #[serde(rename = "tableRowEvenColor")]
+    pub table_row_even_color: String,
+    #[serde(rename = "tableBorderColor")]
+    pub table_border_color: String,
+    #[serde(rename = "fontFamily")]
+    pub font_family: String,
+    #[serde(rename = "fontSize")]
+    pub font_size: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::light()
+    }
+}
+
+impl Theme {
+    macros::setters! {
+        bar_background_color: String => bar_background_color,
+        bar_background_selected_color: String => bar_background_selected_color,
+        bar_progress_color: String => bar_progress_color,
+        bar_progress_selected_color: String => bar_progress_selected_color,
+        project_background_color: String => project_background_color,
+        project_background_selected_color: String => project_background_selected_color,
+        project_progress_color: String => project_progress_color,
+        project_progress_selected_color: String => project_progress_selected_color,
+        milestone_background_color: String => milestone_background_color,
+        milestone_background_selected_color: String => milestone_background_selected_color,
+        bar_label_color: String => bar_label_color,
+        bar_label_outside_color: String => bar_label_outside_color,
+        arrow_color: String => arrow_color,
+        today_color: String => today_color,
+        marker_color: String => marker_color,
+        grid_row_color: String => grid_row_color,
+        grid_row_even_color: String => grid_row_even_color,
+        grid_row_line_color: String => grid_row_line_color,
+        grid_tick_color: String => grid_tick_color,
+        calendar_background_color: String => calendar_background_color,
+        calendar_border_color: String => calendar_border_color,
+        calendar_text_color: String => calendar_text_color,
+        calendar_top_text_color: String => calendar_top_text_color,
+        table_text_color: String => table_text_color,
+        table_background_color: String => table_background_color,
+        table_row_even_color: String => table_row_even_color,
+        table_border_color: String => table_border_color,
+        font_family: String => font_family,
+        font_size: String => font_size,
+    }
+
+    pub fn light() -> Self {
+        Theme {
+            bar_background_color: "#b8c2cc".to_owned(),
+            bar_background_selected_color: "#aeb8c2".to_owned(),
+            bar_progress_color: "#a3a3ff".to_owned(),
+            bar_progress_selected_color: "#8282f5".to_owned(),
+            project_background_color: "#fac465".to_owned(),
+            project_background_selected_color: "#f7bb53".to_owned(),
+            project_progress_color: "#7db59a".to_owned(),
+            project_progress_selected_color: "#59a985".to_owned(),
+            milestone_background_color: "#f1c453".to_owned(),
+            milestone_background_selected_color: "#f29e4c".to_owned(),
+            bar_label_color: "#fff".to_owned(),
+            bar_label_outside_color: "#555".to_owned(),
+            arrow_color: "grey".to_owned(),
+            today_color: "rgba(252, 248, 227, 0.5)".to_owned(),
+            marker_color: "#e74c3c".to_owned(),
+            grid_row_color: "#fff".to_owned(),
+            grid_row_even_color: "#f5f5f5".to_owned(),
+            grid_row_line_color: "#ebeff2".to_owned(),
+            grid_tick_color: "#e6e4e4".to_owned(),
+            calendar_background_color: "#ffffff".to_owned(),
+            calendar_border_color: "#e0e0e0".to_owned(),
+            calendar_text_color: "#333".to_owned(),
+            calendar_top_text_color: "#555".to_owned(),
+            table_text_color: "#212529".to_owned(),
+            table_background_color: "#fff".to_owned(),
+            table_row_even_color: "#f5f5f5".to_owned(),
+            table_border_color: "#e6e4e4".to_owned(),
+            font_family:
+                "Arial, Roboto, Oxygen, Ubuntu, Cantarell, Fira Sans, Droid Sans, Helvetica Neue"
+                    .to_owned(),
+            font_size: "14px".to_owned(),
+        }
+    }
+
+    pub fn dark() -> Self {
+        Theme {
+            bar_background_color: "#4a5563".to_owned(),
+            bar_background_selected_color: "#5b6675".to_owned(),
+            bar_progress_color: "#7c7cf0".to_owned(),
+            bar_progress_selected_color: "#9a9af7".to_owned(),
+            project_background_color: "#b98a2f".to_owned(),
+            project_background_selected_color: "#cc9a3a".to_owned(),
+            project_progress_color: "#4f8f72".to_owned(),
+            project_progress_selected_color: "#5fa685".to_owned(),
+            milestone_background_color: "#d9a93b".to_owned(),
+            milestone_background_selected_color: "#e58a36".to_owned(),
+            bar_label_color: "#f1f1f1".to_owned(),
+            bar_label_outside_color: "#c8c8c8".to_owned(),
+            arrow_color: "#8a8a8a".to_owned(),
+            today_color: "rgba(120, 110, 60, 0.35)".to_owned(),
+            marker_color: "#ff6b5b".to_owned(),
+            grid_row_color: "#1e1f22".to_owned(),
+            grid_row_even_color: "#25262a".to_owned(),
+            grid_row_line_color: "#2f3136".to_owned(),
+            grid_tick_color: "#34363b".to_owned(),
+            calendar_background_color: "#1a1b1e".to_owned(),
+            calendar_border_color: "#34363b".to_owned(),
+            calendar_text_color: "#d4d4d4".to_owned(),
+            calendar_top_text_color: "#b4b4b4".to_owned(),
+            table_text_color: "#d4d4d4".to_owned(),
+            table_background_color: "#1e1f22".to_owned(),
+            table_row_even_color: "#25262a".to_owned(),
+            table_border_color: "#34363b".to_owned(),
+            ..Theme::light()
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Theme {
+            bar_background_color: "#000".to_owned(),
+            bar_background_selected_color: "#000".to_owned(),
+            bar_progress_color: "#0050ef".to_owned(),
+            bar_progress_selected_color: "#003cb3".to_owned(),
+            project_background_color: "#000".to_owned(),
+            project_background_selected_color: "#000".to_owned(),
+            project_progress_color: "#b30000".to_owned(),
+            project_progress_selected_color: "#800000".to_owned(),
+            milestone_background_color: "#b30000".to_owned(),
+            milestone_background_selected_color: "#800000".to_owned(),
+            bar_label_color: "#fff".to_owned(),
+            bar_label_outside_color: "#000".to_owned(),
+            arrow_color: "#000".to_owned(),
+            today_color: "rgba(255, 230, 0, 0.6)".to_owned(),
+            marker_color: "#d00000".to_owned(),
+            grid_row_color: "#fff".to_owned(),
+            grid_row_even_color: "#fff".to_owned(),
+            grid_row_line_color: "#000".to_owned(),
+            grid_tick_color: "#000".to_owned(),
+            calendar_background_color: "#fff".to_owned(),
+            calendar_border_color: "#000".to_owned(),
+            calendar_text_color: "#000".to_owned(),
+            calendar_top_text_color: "#000".to_owned(),
+            table_text_color: "#000".to_owned(),
+            table_background_color: "#fff".to_owned(),
+            table_row_even_color: "#fff".to_owned(),
+            table_border_color: "#000".to_owned(),
+            font_size: "16px".to_owned(),
+            ..Theme::light()
+        }
+    }
+
+    pub fn from_preset(preset: &ThemePreset) -> Self {
+        match preset {
+            ThemePreset::Light => Theme::light(),
+            ThemePreset::Dark => Theme::dark(),
+            ThemePreset::HighContrast => Theme::high_contrast(),
+        }
+    }
+
+    /// Preset picked by `StylingOption.theme` with the explicitly set colours and fonts on top.
+    pub fn from_style(style: &StylingOption) -> Self {
+        let theme = Theme::from_preset(&style.theme.clone().unwrap_or_default());
+        let or = |value: &Option<String>, default: String| value.clone().unwrap_or(default);
+
+        Theme {
+            bar_background_color: or(&style.bar_background_color, theme.bar_background_color),
+            bar_background_selected_color: or(
+                &style.bar_background_selected_color,
+                theme.bar_background_selected_color,
+            ),
+            bar_progress_color: or(&style.bar_progress_color, theme.bar_progress_color),
+            bar_progress_selected_color: or(
+                &style.bar_progress_selected_color,
+                theme.bar_progress_selected_color,
+            ),
+            project_background_color: or(
+                &style.project_background_color,
+                theme.project_background_color,
+            ),
+            project_background_selected_color: or(
+                &style.project_background_selected_color,
+                theme.project_background_selected_color,
+            ),
+            project_progress_color: or(&style.project_progress_color, theme.project_progress_color),
+            project_progress_selected_color: or(
+                &style.project_progress_selected_color,
+                theme.project_progress_selected_color,
+            ),
+            milestone_background_color: or(
+                &style.milestone_background_color,
+                theme.milestone_background_color,
+            ),
+            milestone_background_selected_color: or(
+                &style.milestone_background_selected_color,
+                theme.milestone_background_selected_color,
+            ),
+            arrow_color: or(&style.arrow_color, theme.arrow_color),
+            today_color: or(&style.today_color, theme.today_color),
+            font_family: or(&style.font_family, theme.font_family),
+            font_size: or(&style.font_size, theme.font_size),
+            ..theme
+        }
+    }
+
+    /// `(name, value)` pairs of the CSS custom properties.
+    pub fn variables(&self) -> Vec<(&'static str, String)> {
+        vec![
+            (
+                "--gantt-bar-background-color",
+                self.bar_background_color.clone(),
+            ),
+            (
+                "--gantt-bar-background-selected-color",
+                self.bar_background_selected_color.clone(),
+            ),
+            (
+                "--gantt-bar-progress-color",
+                self.bar_progress_color.clone(),
+            ),
+            (
+                "--gantt-bar-progress-selected-color",
+                self.bar_progress_selected_color.clone(),
+            ),
+            (
+                "--gantt-project-background-color",
+                self.project_background_color.clone(),
+            ),
+            (
+                "--gantt-project-background-selected-color",
+                self.project_background_selected_color.clone(),
+            ),
+            (
+                "--gantt-project-progress-color",
+                self.project_progress_color.clone(),
+            ),
+            (
+                "--gantt-project-progress-selected-color",
+                self.project_progress_selected_color.clone(),
+            ),
+            (
+                "--gantt-milestone-background-color",
+                self.milestone_background_color.clone(),
+            ),
+            (
+                "--gantt-milestone-background-selected-color",
+                self.milestone_background_selected_color.clone(),
+            ),
+            ("--gantt-bar-label-color", self.bar_label_color.clone()),
+            (
+                "--gantt-bar-label-outside-color",
+                self.bar_label_outside_color.clone(),
+            ),
+            ("--gantt-arrow-color", self.arrow_color.clone()),
+            ("--gantt-today-color", self.today_color.clone()),
+            ("--gantt-marker-color", self.marker_color.clone()),
+            ("--gantt-grid-row-color", self.grid_row_color.clone()),
+            (
+                "--gantt-grid-row-even-color",
+                self.grid_row_even_color.clone(),
+            ),
+            (
+                "--gantt-grid-row-line-color",
+                self.grid_row_line_color.clone(),
+            ),
+            ("--gantt-grid-tick-color", self.grid_tick_color.clone()),
+            (
+                "--gantt-calendar-background-color",
+                self.calendar_background_color.clone(),
+            ),
+            (
+                "--gantt-calendar-border-color",
+                self.calendar_border_color.clone(),
+            ),
+            (
+                "--gantt-calendar-text-color",
+                self.calendar_text_color.clone(),
+            ),
+            (
+                "--gantt-calendar-top-text-color",
+                self.calendar_top_text_color.clone(),
+            ),
+            ("--gantt-table-text-color", self.table_text_color.clone()),
+            (
+                "--gantt-table-background-color",
+                self.table_background_color.clone(),
+            ),
+            (
+                "--gantt-table-row-even-color",
+                self.table_row_even_color.clone(),
+            ),
+            (
+                "--gantt-table-border-color",
+                self.table_border_color.clone(),
+            ),
+            ("--gantt-font-family", self.font_family.clone()),
+            ("--gantt-font-size", self.font_size.clone()),
+        ]
+    }
+
+    /// Rule declaring all variables for `selector`. `:where` keeps its specificity at zero,
+    /// so any host rule targeting the chart wins.
+    pub fn css(&self, selector: &str) -> String {
+        let declarations = self
+            .variables()
+            .iter()
+            .map(|(name, value)| format!("{}: {};", name, value))
+            .collect::<Vec<String>>()
+            .join(" ");
+        format!(":where({}) {{ {} }}", selector, declarations)
+    }
+}
+
+/// Reference to a theme variable, `value` is used when the variable is not defined.
+pub(crate) fn var(name: &str, value: &str) -> String {
+    format!("var({}, {})", name, value)
+}
@@ -0,0 +1,267 @@
+use std::fmt::Write;
+use std::str::FromStr;
+
+use chrono::{DateTime, FixedOffset, Local, LocalResult, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use super::view_mode::{Scale, TimeScale};
+
+/// Time zone the chart is displayed in.
+///
+/// Internally every date is an UTC instant, the zone only matters for parsing dates without
+/// an offset, for the calendar labels and for the day/week/month column boundaries.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub enum DisplayTimeZone {
+    /// Time zone of the browser
+    #[default]
+    Local,
+    Utc,
+    Fixed(FixedOffset),
+    Named(Tz),
+}
+
+impl DisplayTimeZone {
+    /// Accepts `local`, `UTC`, an offset like `+02:00` or an IANA name like `Europe/Berlin`.
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("local") {
+            return Some(DisplayTimeZone::Local);
+        }
+        if value.eq_ignore_ascii_case("utc") || value == "Z" {
+            return Some(DisplayTimeZone::Utc);
+        }
+        if let Ok(offset) = FixedOffset::from_str(value) {
+            return Some(DisplayTimeZone::Fixed(offset));
+        }
+        Tz::from_str(value).ok().map(DisplayTimeZone::Named)
+    }
+
+    /// Wall clock time of the UTC instant.
+    pub fn to_local(&self, utc: NaiveDateTime) -> NaiveDateTime {
+        match self {
+            DisplayTimeZone::Local => Local.from_utc_datetime(&utc).naive_local(),
+            DisplayTimeZone::Utc => utc,
+            DisplayTimeZone::Fixed(offset) => offset.from_utc_datetime(&utc).naive_local(),
+            DisplayTimeZone::Named(tz) => tz.from_utc_datetime(&utc).naive_local(),
+        }
+    }
+
+    /// UTC instant of the wall clock time. Ambiguous times (DST end) resolve to the earlier
+    /// instant, skipped times (DST start) are moved forward past the gap.
+    pub fn to_utc(&self, local: NaiveDateTime) -> NaiveDateTime {
+        match self {
+            DisplayTimeZone::Local => resolve(&Local, local),
+            DisplayTimeZone::Utc => local,
+            DisplayTimeZone::Fixed(offset) => resolve(offset, local),
+            DisplayTimeZone::Named(tz) => resolve(tz, local),
+        }
+    }
+}
+
+fn resolve<Z: TimeZone>(tz: &Z, local: NaiveDateTime) -> NaiveDateTime {
+    let mut candidate = local;
+    // gaps are at most a couple of hours, step over them
+    for _ in 0..8 {
+        match tz.from_local_datetime(&candidate) {
+            LocalResult::Single(date) | LocalResult::Ambiguous(date, _) => return date.naive_utc(),
+            LocalResult::None => candidate += chrono::Duration::minutes(30),
+        }
+    }
+    local
+}
+
+/// Parses a task date into an UTC instant.
+///
+/// The value may carry its own zone, as an RFC 3339 string, as `fmt` followed by an offset
+/// (`2023-04-20 01:46:39 +02:00`) or as `fmt` followed by an IANA name
+/// (`2023-04-20 01:46:39 Europe/Berlin`). Values without a zone are read in `tz`.
+pub fn parse_date(value: &str, fmt: &str, tz: &DisplayTimeZone) -> Result<NaiveDateTime, String> {
+    let value = value.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Ok(date.naive_utc());
+    }
+    for with_offset in [format!("{} %#z", fmt), format!("{}%#z", fmt)] {
+        if let Ok(date) = DateTime::parse_from_str(value, &with_offset) {
+            return Ok(date.naive_utc());
+        }
+    }
+    if let Some((date, zone)) = value.rsplit_once(' ') {
+        if let (Ok(date), Ok(zone)) = (NaiveDateTime::parse_from_str(date, fmt), Tz::from_str(zone))
+        {
+            return Ok(DisplayTimeZone::Named(zone).to_utc(date));
+        }
+    }
+
+    NaiveDateTime::parse_from_str(value, fmt)
+        .map(|date| tz.to_utc(date))
+        .map_err(|err| format!("{}: {}", value, err))
+}
+
+/// Writes an UTC instant as a task date, `fmt` in `tz`. Formats with a zone of their own fall
+/// back to RFC 3339.
+pub fn format_date(date: NaiveDateTime, fmt: &str, tz: &DisplayTimeZone) -> String {
+    let mut value = String::new();
+    match write!(value, "{}", tz.to_local(date).format(fmt)) {
+        Ok(()) => value,
+        Err(_) => date.and_utc().to_rfc3339(),
+    }
+}
+
+/// Writes an UTC instant for display in `style`: `date` for the day only, `relative` like
+/// `in 3 days` or `2 hours ago`, otherwise a format like the one of the task dates.
+pub fn display_date(date: NaiveDateTime, style: &str, tz: &DisplayTimeZone) -> String {
+    match style {
+        "date" => format_date(date, "%Y-%m-%d", tz),
+        "relative" => relative(date, now(), tz),
+        _ => format_date(date, style, tz),
+    }
+}
+
+/// `date` relative to `now`, by the hour on the same day of `tz`, by calendar days otherwise.
+pub fn relative(date: NaiveDateTime, now: NaiveDateTime, tz: &DisplayTimeZone) -> String {
+    let days = (tz.to_local(date).date() - tz.to_local(now).date()).num_days();
+    let (count, unit) = match days.abs() {
+        0 => {
+            let minutes = (date - now).num_minutes();
+            match minutes.abs() {
+                0 => return "now".to_owned(),
+                1..=59 => (minutes, "minute"),
+                _ => ((date - now).num_hours(), "hour"),
+            }
+        }
+        1 if days > 0 => return "tomorrow".to_owned(),
+        1 => return "yesterday".to_owned(),
+        2..=13 => (days, "day"),
+        14..=59 => (days / 7, "week"),
+        60..=364 => (days / 30, "month"),
+        _ => (days / 365, "year"),
+    };
+    let plural = if count.abs() == 1 { "" } else { "s" };
+    if count < 0 {
+        format!("{} {}{} ago", -count, unit, plural)
+    } else {
+        format!("in {} {}{}", count, unit, plural)
+    }
+}
+
+/// Current instant, in UTC like every other date of the chart.
+pub fn now() -> NaiveDateTime {
+    Utc::now().naive_utc()
+}
+
+/// Start of the column containing `date`, on the wall clock of `tz`.
+pub(crate) fn snap(scale: &Scale, date: NaiveDateTime, tz: &DisplayTimeZone) -> NaiveDateTime {
+    tz.to_utc(scale.start_of(tz.to_local(date)))
+}
+
+/// Next (or previous) column boundary. Fixed length steps are exact durations, so an hour
+/// column is always an hour long even across a DST change; quarter days and longer follow the
+/// wall clock of `tz`, so snapped columns keep starting at local 00, 06, 12 and 18 o'clock.
+pub(crate) fn step(
+    scale: &Scale,
+    date: NaiveDateTime,
+    add: bool,
+    tz: &DisplayTimeZone,
+) -> NaiveDateTime {
+    if scale.fixed_length() {
+        scale.step(date, add)
+    } else {
+        // a boundary moved forward out of a DST gap snaps back onto the grid
+        tz.to_utc(scale.start_of(scale.step(tz.to_local(date), add)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Duration, NaiveDate};
+
+    use super::*;
+    use crate::schemas::ViewMode;
+    use crate::view_mode::ScaleOptions;
+
+    fn utc(month: u32, day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, month, day).unwrap().and_hms_opt(hour, 0, 0).unwrap()
+    }
+
+    fn berlin() -> DisplayTimeZone {
+        DisplayTimeZone::parse("Europe/Berlin").unwrap()
+    }
+
+    #[test]
+    fn quarter_days_follow_the_wall_clock_at_spring_forward() {
+        let scale = Scale::new(ViewMode::QuarterDay, ScaleOptions::default());
+        let tz = berlin();
+        // 07:00 CEST on 31 March is in the column from 06:00 CEST
+        assert_eq!(snap(&scale, utc(3, 31, 5), &tz), utc(3, 31, 4));
+        // midnight CET to 06:00 CEST is five hours
+        assert_eq!(step(&scale, utc(3, 30, 23), true, &tz), utc(3, 31, 4));
+        assert_eq!(step(&scale, utc(3, 31, 4), false, &tz), utc(3, 30, 23));
+        assert_eq!(step(&scale, utc(3, 31, 4), true, &tz), utc(3, 31, 10));
+    }
+
+    #[test]
+    fn quarter_days_follow_the_wall_clock_at_fall_back() {
+        let scale = Scale::new(ViewMode::QuarterDay, ScaleOptions::default());
+        let tz = berlin();
+        // 07:00 CET on 27 October is in the column from 06:00 CET
+        assert_eq!(snap(&scale, utc(10, 27, 6), &tz), utc(10, 27, 5));
+        // midnight CEST to 06:00 CET is seven hours
+        assert_eq!(step(&scale, utc(10, 26, 22), true, &tz), utc(10, 27, 5));
+        assert_eq!(step(&scale, utc(10, 27, 5), false, &tz), utc(10, 26, 22));
+        assert_eq!(step(&scale, utc(10, 27, 5), true, &tz), utc(10, 27, 11));
+    }
+
+    #[test]
+    fn half_days_follow_the_wall_clock() {
+        let scale = Scale::new(ViewMode::HalfDay, ScaleOptions::default());
+        let tz = berlin();
+        assert_eq!(snap(&scale, utc(3, 31, 9), &tz), utc(3, 30, 23));
+        assert_eq!(step(&scale, utc(3, 30, 23), true, &tz), utc(3, 31, 10));
+        assert_eq!(snap(&scale, utc(10, 27, 12), &tz), utc(10, 27, 11));
+        assert_eq!(step(&scale, utc(10, 26, 22), true, &tz), utc(10, 27, 11));
+        assert_eq!(step(&scale, utc(10, 27, 11), false, &tz), utc(10, 26, 22));
+    }
+
+    #[test]
+    fn hours_keep_their_length() {
+        let scale = Scale::new(ViewMode::Hour, ScaleOptions::default());
+        let tz = berlin();
+        assert_eq!(step(&scale, utc(3, 31, 0), true, &tz), utc(3, 31, 1));
+        assert_eq!(step(&scale, utc(10, 27, 0), true, &tz), utc(10, 27, 1));
+    }
+
+    #[test]
+    fn relative_dates_within_a_day() {
+        let tz = DisplayTimeZone::default();
+        let now = utc(1, 10, 12);
+        assert_eq!(relative(now, now, &tz), "now");
+        assert_eq!(relative(now + Duration::minutes(1), now, &tz), "in 1 minute");
+        assert_eq!(relative(now - Duration::minutes(59), now, &tz), "59 minutes ago");
+        assert_eq!(relative(utc(1, 10, 15), now, &tz), "in 3 hours");
+    }
+
+    #[test]
+    fn relative_dates_count_calendar_days() {
+        let tz = DisplayTimeZone::default();
+        let now = utc(1, 10, 20);
+        assert_eq!(relative(utc(1, 11, 1), now, &tz), "tomorrow");
+        assert_eq!(relative(utc(1, 9, 23), now, &tz), "yesterday");
+        assert_eq!(relative(utc(1, 13, 0), now, &tz), "in 3 days");
+        // 23:00 and midnight in Berlin are on different days
+        assert_eq!(relative(utc(1, 10, 23), utc(1, 10, 22), &berlin()), "tomorrow");
+    }
+
+    #[test]
+    fn relative_dates_switch_units() {
+        let tz = DisplayTimeZone::default();
+        let now = utc(1, 1, 0);
+        let days = |days: i64| now + Duration::days(days);
+        assert_eq!(relative(days(13), now, &tz), "in 13 days");
+        assert_eq!(relative(days(14), now, &tz), "in 2 weeks");
+        assert_eq!(relative(days(-14), now, &tz), "2 weeks ago");
+        assert_eq!(relative(days(59), now, &tz), "in 8 weeks");
+        assert_eq!(relative(days(60), now, &tz), "in 2 months");
+        assert_eq!(relative(days(364), now, &tz), "in 12 months");
+        assert_eq!(relative(days(365), now, &tz), "in 1 year");
+    }
+}
@@ -0,0 +1,282 @@
+use std::rc::Rc;
+
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveDateTime, Timelike, Weekday};
+use yew::Html;
+
+use super::schemas::{CalendarProps, ViewMode};
+
+/// Column layout of a view mode. Implement it to add a view mode of your own and pass it as
+/// [`ViewMode::Custom`].
+///
+/// Dates are wall clock times of the display time zone.
+pub trait TimeScale {
+    /// Start of the column containing `date`.
+    fn start_of(&self, date: NaiveDateTime) -> NaiveDateTime;
+
+    /// Next (or previous) column boundary.
+    fn step(&self, date: NaiveDateTime, add: bool) -> NaiveDateTime;
+
+    /// Columns of an exact duration are stepped on the UTC timeline, so they keep their length
+    /// across DST changes. Otherwise they follow the wall clock.
+    fn fixed_length(&self) -> bool {
+        false
+    }
+
+    /// Top and bottom calendar label templates, see [`super::schemas::CalendarLabel`].
+    fn labels(&self) -> (String, String) {
+        ("{month_year}".to_owned(), "%d".to_owned())
+    }
+
+    /// Whether two neighbouring columns share one top label.
+    fn same_top(&self, a: &NaiveDateTime, b: &NaiveDateTime) -> bool {
+        (a.year(), a.month()) == (b.year(), b.month())
+    }
+
+    /// Bottom and top calendar rows. The default renders `labels` with `same_top` grouping.
+    fn header(&self, props: &CalendarProps) -> (Html, Html) {
+        super::svg_view::calendar_header(self, props)
+    }
+}
+
+/// View mode defined by the application.
+#[derive(Clone)]
+pub struct CustomViewMode {
+    /// Name of the mode, `calendarLabels` entries name it in `custom`
+    pub name: String,
+    pub scale: Rc<dyn TimeScale>,
+}
+
+impl CustomViewMode {
+    pub fn new(name: impl Into<String>, scale: impl TimeScale + 'static) -> Self {
+        CustomViewMode {
+            name: name.into(),
+            scale: Rc::new(scale),
+        }
+    }
+}
+
+impl PartialEq for CustomViewMode {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && Rc::ptr_eq(&self.scale, &other.scale)
+    }
+}
+
+impl Eq for CustomViewMode {}
+
+impl std::fmt::Debug for CustomViewMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CustomViewMode").field(&self.name).finish()
+    }
+}
+
+/// Settings of the configurable view modes.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ScaleOptions {
+    pub week_start: Weekday,
+    /// Length of a `Minute` column in minutes
+    pub minute_step: u32,
+    /// Length of a `Sprint` column in weeks
+    pub sprint_weeks: u32,
+    /// Start of any sprint, the others follow back to back
+    pub sprint_anchor: NaiveDateTime,
+    /// First month of the fiscal year, from 1 to 12
+    pub fiscal_year_start_month: u32,
+}
+
+impl Default for ScaleOptions {
+    fn default() -> Self {
+        ScaleOptions {
+            week_start: Weekday::Mon,
+            minute_step: 15,
+            sprint_weeks: 2,
+            sprint_anchor: NaiveDate::from_ymd_opt(1970, 1, 5)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap(),
+            fiscal_year_start_month: 1,
+        }
+    }
+}
+
+/// View modes zooming switches between, from the finest to the coarsest.
+pub const ZOOM_LEVELS: [ViewMode; 9] = [
+    ViewMode::Minute,
+    ViewMode::Hour,
+    ViewMode::QuarterDay,
+    ViewMode::HalfDay,
+    ViewMode::Day,
+    ViewMode::Week,
+    ViewMode::Month,
+    ViewMode::QuarterYear,
+    ViewMode::Year,
+];
+
+/// A view mode with its settings, the time scale of the chart.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Scale {
+    pub mode: ViewMode,
+    pub options: ScaleOptions,
+}
+
+impl Scale {
+    pub fn new(mode: ViewMode, options: ScaleOptions) -> Self {
+        Scale { mode, options }
+    }
+
+    /// Length of the column containing `date` in minutes.
+    pub fn column_minutes(&self, date: NaiveDateTime) -> f64 {
+        let start = self.start_of(date);
+        (self.step(start, true) - start).num_minutes() as f64
+    }
+
+    /// 1-based number of the sprint containing `date`, counted from the anchor.
+    pub fn sprint(&self, date: &NaiveDateTime) -> i64 {
+        let days = (date.date() - self.options.sprint_anchor.date()).num_days();
+        days.div_euclid(self.sprint_days()) + 1
+    }
+
+    /// Fiscal year of `date`, named by the calendar year it ends in.
+    pub fn fiscal_year(&self, date: &NaiveDateTime) -> i32 {
+        let start_month = self.options.fiscal_year_start_month;
+        if start_month > 1 && date.month() >= start_month {
+            date.year() + 1
+        } else {
+            date.year()
+        }
+    }
+
+    /// 1-based quarter of the fiscal year of `date`.
+    pub fn fiscal_quarter(&self, date: &NaiveDateTime) -> u32 {
+        (date.month() + 12 - self.options.fiscal_year_start_month) % 12 / 3 + 1
+    }
+
+    fn sprint_days(&self) -> i64 {
+        (self.options.sprint_weeks.max(1) * 7) as i64
+    }
+
+    /// First day of the `months` long period containing `date`, periods start in `first_month`.
+    fn start_of_months(date: &NaiveDateTime, months: i32, first_month: u32) -> NaiveDateTime {
+        let offset = first_month as i32 - 1;
+        let total = date.year() * 12 + date.month0() as i32;
+        let start = offset + (total - offset).div_euclid(months) * months;
+        NaiveDate::from_ymd_opt(start.div_euclid(12), start.rem_euclid(12) as u32 + 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+    }
+}
+
+impl TimeScale for Scale {
+    fn start_of(&self, date: NaiveDateTime) -> NaiveDateTime {
+        let midnight = date.date().and_hms_opt(0, 0, 0).unwrap();
+        let minutes = |step: u32| {
+            let minute = date.hour() * 60 + date.minute();
+            midnight + Duration::minutes((minute / step * step) as i64)
+        };
+        let fiscal_month = self.options.fiscal_year_start_month;
+        match &self.mode {
+            ViewMode::Minute => minutes(self.options.minute_step.clamp(1, 1440)),
+            ViewMode::Hour => minutes(60),
+            ViewMode::QuarterDay => minutes(6 * 60),
+            ViewMode::HalfDay => minutes(12 * 60),
+            ViewMode::Day => midnight,
+            ViewMode::Week => {
+                let offset = (date.weekday().num_days_from_monday() + 7
+                    - self.options.week_start.num_days_from_monday())
+                    % 7;
+                midnight - Duration::days(offset as i64)
+            }
+            ViewMode::Sprint => {
+                let anchor = self.options.sprint_anchor.date().and_hms_opt(0, 0, 0).unwrap();
+                let days = (midnight - anchor).num_days();
+                anchor + Duration::days(days.div_euclid(self.sprint_days()) * self.sprint_days())
+            }
+            ViewMode::Month => Scale::start_of_months(&date, 1, 1),
+            ViewMode::QuarterYear => Scale::start_of_months(&date, 3, 1),
+            ViewMode::Year => Scale::start_of_months(&date, 12, 1),
+            ViewMode::FiscalQuarter => Scale::start_of_months(&date, 3, fiscal_month),
+            ViewMode::FiscalYear => Scale::start_of_months(&date, 12, fiscal_month),
+            ViewMode::Custom(custom) => custom.scale.start_of(date),
+        }
+    }
+
+    fn step(&self, date: NaiveDateTime, add: bool) -> NaiveDateTime {
+        let duration = |duration: Duration| {
+            if add {
+                date.checked_add_signed(duration)
+            } else {
+                date.checked_sub_signed(duration)
+            }
+        };
+        let months = |months: u32| {
+            if add {
+                date.checked_add_months(Months::new(months))
+            } else {
+                date.checked_sub_months(Months::new(months))
+            }
+        };
+        match &self.mode {
+            ViewMode::Minute => duration(Duration::minutes(
+                self.options.minute_step.clamp(1, 1440) as i64,
+            )),
+            ViewMode::Hour => duration(Duration::hours(1)),
+            ViewMode::QuarterDay => duration(Duration::hours(6)),
+            ViewMode::HalfDay => duration(Duration::hours(12)),
+            ViewMode::Day => duration(Duration::days(1)),
+            ViewMode::Week => duration(Duration::days(7)),
+            ViewMode::Sprint => duration(Duration::days(self.sprint_days())),
+            ViewMode::Month => months(1),
+            ViewMode::QuarterYear | ViewMode::FiscalQuarter => months(3),
+            ViewMode::Year | ViewMode::FiscalYear => months(12),
+            ViewMode::Custom(custom) => Some(custom.scale.step(date, add)),
+        }
+        .unwrap()
+    }
+
+    fn fixed_length(&self) -> bool {
+        match &self.mode {
+            ViewMode::Minute | ViewMode::Hour => true,
+            ViewMode::Custom(custom) => custom.scale.fixed_length(),
+            _ => false,
+        }
+    }
+
+    fn labels(&self) -> (String, String) {
+        let (top, bottom) = match &self.mode {
+            ViewMode::Minute => ("%a, {day_month} %H:00", "%M"),
+            ViewMode::Hour | ViewMode::QuarterDay | ViewMode::HalfDay => ("%a, {day_month}", "%H"),
+            ViewMode::Day => ("{month_year}", "%d"),
+            ViewMode::Week => ("{month_year}", "W{week}"),
+            ViewMode::Sprint => ("{month_year}", "S{sprint}"),
+            ViewMode::Month => ("%Y", "%b"),
+            ViewMode::QuarterYear => ("%Y", "Q{quarter}"),
+            ViewMode::Year => ("%Y", "%Y"),
+            ViewMode::FiscalQuarter => ("FY{fiscal_year}", "Q{fiscal_quarter}"),
+            ViewMode::FiscalYear => ("FY{fiscal_year}", "FY{fiscal_year}"),
+            ViewMode::Custom(custom) => return custom.scale.labels(),
+        };
+        (top.to_owned(), bottom.to_owned())
+    }
+
+    fn same_top(&self, a: &NaiveDateTime, b: &NaiveDateTime) -> bool {
+        match &self.mode {
+            ViewMode::Minute => (a.date(), a.hour()) == (b.date(), b.hour()),
+            ViewMode::Hour | ViewMode::QuarterDay | ViewMode::HalfDay => a.date() == b.date(),
+            ViewMode::Day | ViewMode::Week | ViewMode::Sprint => {
+                (a.year(), a.month()) == (b.year(), b.month())
+            }
+            ViewMode::Month | ViewMode::QuarterYear | ViewMode::Year => a.year() == b.year(),
+            ViewMode::FiscalQuarter | ViewMode::FiscalYear => {
+                self.fiscal_year(a) == self.fiscal_year(b)
+            }
+            ViewMode::Custom(custom) => custom.scale.same_top(a, b),
+        }
+    }
+
+    fn header(&self, props: &CalendarProps) -> (Html, Html) {
+        match &self.mode {
+            ViewMode::Custom(custom) => custom.scale.header(props),
+            _ => super::svg_view::calendar_header(self, props),
+        }
+    }
+}